@@ -0,0 +1,25 @@
+//! Фаззинг разбора HTTP запросов: копирующего `HttpRequest::parse`
+//! и zero-copy `parse_raw` на одних и тех же входах
+//!
+//! Запуск (нужны nightly и `cargo install cargo-fuzz`):
+//!
+//! ```text
+//! cargo +nightly fuzz run parse_http_request
+//! cargo +nightly fuzz run parse_http_request -- -max_total_time=60
+//! ```
+//!
+//! Корпус сохраняется в `fuzz/corpus/parse_http_request`, входы,
+//! вызвавшие панику, — в `fuzz/artifacts/parse_http_request`. Найденный
+//! вход воспроизводится командой `cargo +nightly fuzz run parse_http_request <файл>`
+//! и затем закрепляется регрессионным тестом в `src/networking.rs`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_advanced_course::networking::{parse_raw, HttpRequest};
+
+fuzz_target!(|data: &[u8]| {
+    // Любые байты допустимы: разбор может вернуть ошибку, но не паниковать
+    let _ = HttpRequest::parse(data);
+    let _ = parse_raw(data);
+});
@@ -0,0 +1,2 @@
+-- Мягкое удаление пользователей
+ALTER TABLE users ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
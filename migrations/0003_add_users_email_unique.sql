@@ -0,0 +1,2 @@
+-- Уникальный email нужен для upsert через ON CONFLICT (email)
+ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);
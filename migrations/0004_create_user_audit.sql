@@ -0,0 +1,40 @@
+-- Журнал изменений пользователей, заполняется триггером
+CREATE TABLE IF NOT EXISTS user_audit (
+    id BIGSERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL,
+    operation TEXT NOT NULL,
+    old_values JSONB,
+    new_values JSONB,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS user_audit_user_id_idx ON user_audit (user_id);
+
+CREATE OR REPLACE FUNCTION audit_users() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO user_audit (user_id, operation, new_values)
+        VALUES (NEW.id, 'INSERT', to_jsonb(NEW));
+        RETURN NEW;
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- Мягкое удаление записывается как DELETE
+        INSERT INTO user_audit (user_id, operation, old_values, new_values)
+        VALUES (
+            NEW.id,
+            CASE WHEN OLD.deleted_at IS NULL AND NEW.deleted_at IS NOT NULL THEN 'DELETE' ELSE 'UPDATE' END,
+            to_jsonb(OLD),
+            to_jsonb(NEW)
+        );
+        RETURN NEW;
+    ELSE
+        INSERT INTO user_audit (user_id, operation, old_values)
+        VALUES (OLD.id, 'DELETE', to_jsonb(OLD));
+        RETURN OLD;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS users_audit ON users;
+CREATE TRIGGER users_audit
+AFTER INSERT OR UPDATE OR DELETE ON users
+FOR EACH ROW EXECUTE FUNCTION audit_users();
@@ -5,13 +5,20 @@
 //! - Поиск
 //! - Графовые алгоритмы
 //! - Динамическое программирование
+//! - Строковые алгоритмы
 
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
 use std::iter::FromIterator;
 
+use crate::data_structures::{DisjointSet, WeightedGraph};
+
 /// Структура для сортируемых элементов
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Упорядочивается по `value`, затем по `id`; `metadata` разрешает
+/// оставшиеся совпадения, чтобы порядок был согласован с равенством.
+#[derive(Debug, Clone)]
 pub struct SortableItem {
     pub id: i32,
     pub value: f64,
@@ -29,16 +36,106 @@ pub fn new(id: i32, value: f64, metadata: String) -> Self {
     }
 }
 
+/// Компактный ключ сортировки `SortableItem`: значение, затем идентификатор
+#[derive(Debug, Clone, Copy)]
+pub struct SortKey {
+    pub value: f64,
+    pub id: i32,
+}
+
+impl From<&SortableItem> for SortKey {
+    fn from(item: &SortableItem) -> Self {
+        Self {
+            value: item.value,
+            id: item.id,
+        }
+    }
+}
+
+impl PartialEq for SortKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SortKey {}
+
+impl PartialOrd for SortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // total_cmp задает полный порядок и для NaN
+        self.value
+            .total_cmp(&other.value)
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+impl PartialEq for SortableItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SortableItem {}
+
+impl PartialOrd for SortableItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SortableItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        SortKey::from(self)
+            .cmp(&SortKey::from(other))
+            .then_with(|| self.metadata.cmp(&other.metadata))
+    }
+}
+
+/// Поле, по которому сортируются элементы, выбирается во время выполнения
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    Id,
+    Value,
+    Metadata,
+}
+
+/// Устойчивая сортировка элементов по выбранному полю
+pub fn sort_items_by(items: &mut [SortableItem], field: SortField) {
+    match field {
+        SortField::Id => items.sort_by_key(|item| item.id),
+        SortField::Value => items.sort_by(|a, b| a.value.total_cmp(&b.value)),
+        SortField::Metadata => items.sort_by(|a, b| a.metadata.cmp(&b.metadata)),
+    }
+}
+
+/// Минимальная длина серии в `tim_sort`, короче которой серия
+/// дополняется сортировкой вставками
+const TIM_SORT_MIN_RUN: usize = 64;
+
+/// Размер подмассива, начиная с которого `parallel_sort` переходит
+/// на последовательную быструю сортировку
+const PARALLEL_SORT_THRESHOLD: usize = 2048;
+
 /// Реализация алгоритмов сортировки
 pub struct SortingAlgorithms;
 
 impl SortingAlgorithms {
     /// Быстрая сортировка
+    ///
+    /// Опорный элемент выбирается медианой трех, поэтому уже упорядоченный
+    /// вход не приводит к квадратичному времени.
     pub fn quick_sort<T: Ord>(arr: &mut [T]) {
         if arr.len() <= 1 {
             return;
         }
 
+        move_median_to_end(arr);
         let pivot = partition(arr);
         let (left, right) = arr.split_at_mut(pivot);
         
@@ -46,6 +143,27 @@ pub fn quick_sort<T: Ord>(arr: &mut [T]) {
         Self::quick_sort(&mut right[1..]);
     }
 
+    /// Параллельная быстрая сортировка на пуле потоков rayon
+    ///
+    /// После разбиения обе части сортируются через `rayon::join`, который
+    /// отдает одну из них свободному потоку пула. Опорный элемент выбирается
+    /// медианой трех, чтобы уже отсортированный вход не вырождал рекурсию.
+    /// Короткие подмассивы досортировываются последовательным `quick_sort`.
+    pub fn parallel_sort<T: Ord + Send>(arr: &mut [T]) {
+        if arr.len() <= PARALLEL_SORT_THRESHOLD {
+            Self::quick_sort(arr);
+            return;
+        }
+
+        move_median_to_end(arr);
+        let pivot = partition(arr);
+        let (left, right) = arr.split_at_mut(pivot);
+        rayon::join(
+            || Self::parallel_sort(left),
+            || Self::parallel_sort(&mut right[1..]),
+        );
+    }
+
     /// Сортировка слиянием
     pub fn merge_sort<T: Ord + Clone>(arr: &mut [T]) {
         if arr.len() <= 1 {
@@ -53,12 +171,65 @@ pub fn merge_sort<T: Ord + Clone>(arr: &mut [T]) {
         }
 
         let mid = arr.len() / 2;
-        let (left, right) = arr.split_at_mut(mid);
-        
-        Self::merge_sort(left);
-        Self::merge_sort(right);
-        
-        merge(arr, left, right);
+        Self::merge_sort(&mut arr[..mid]);
+        Self::merge_sort(&mut arr[mid..]);
+
+        // merge пишет результат в arr, поэтому половины копируются заранее
+        let left = arr[..mid].to_vec();
+        let right = arr[mid..].to_vec();
+        merge(arr, &left, &right);
+    }
+
+    /// Адаптивная сортировка в стиле Timsort
+    ///
+    /// Массив разбивается на уже упорядоченные серии (убывающие разворачиваются),
+    /// короткие серии добиваются до `TIM_SORT_MIN_RUN` элементов сортировкой
+    /// вставками, после чего соседние серии попарно сливаются. На почти
+    /// отсортированных данных работает за время, близкое к O(n).
+    pub fn tim_sort<T: Ord + Clone>(arr: &mut [T]) {
+        let len = arr.len();
+        let mut runs = Vec::new();
+
+        let mut start = 0;
+        while start < len {
+            let mut end = start + 1;
+            if end < len && arr[end] < arr[end - 1] {
+                // Строго убывающая серия: строгость сохраняет устойчивость
+                while end < len && arr[end] < arr[end - 1] {
+                    end += 1;
+                }
+                arr[start..end].reverse();
+            } else {
+                while end < len && arr[end] >= arr[end - 1] {
+                    end += 1;
+                }
+            }
+
+            if end - start < TIM_SORT_MIN_RUN {
+                end = (start + TIM_SORT_MIN_RUN).min(len);
+                Self::insertion_sort(&mut arr[start..end]);
+            }
+
+            runs.push((start, end));
+            start = end;
+        }
+
+        while runs.len() > 1 {
+            let mut merged = Vec::with_capacity(runs.len() / 2 + 1);
+            for pair in runs.chunks(2) {
+                match *pair {
+                    [(start, mid), (_, end)] => {
+                        let left = arr[start..mid].to_vec();
+                        let right = arr[mid..end].to_vec();
+                        merge(&mut arr[start..end], &left, &right);
+                        merged.push((start, end));
+                    }
+                    [run] => merged.push(run),
+                    _ => unreachable!(),
+                }
+            }
+            runs = merged;
+        }
     }
 
     /// Сортировка вставками
@@ -72,6 +243,24 @@ pub fn insertion_sort<T: Ord>(arr: &mut [T]) {
         }
     }
 
+    /// Сортировка подсчетом для чисел из диапазона `0..=max_value`
+    ///
+    /// Работает за O(n + max_value) без сравнений элементов.
+    /// Паникует, если в массиве встречается значение больше `max_value`.
+    pub fn counting_sort(arr: &mut [u32], max_value: u32) {
+        counting_sort_by_key(arr, max_value as usize + 1, |value| value as usize);
+    }
+
+    /// Поразрядная сортировка (LSD) по байтам
+    ///
+    /// Четыре прохода устойчивой сортировки подсчетом, по одному на каждый
+    /// 8-битный разряд `u32`, итого O(4 * (n + 256)).
+    pub fn radix_sort(arr: &mut [u32]) {
+        for shift in (0..32).step_by(8) {
+            counting_sort_by_key(arr, 256, |value| ((value >> shift) & 0xFF) as usize);
+        }
+    }
+
     /// Сортировка кучей (Heap Sort)
     pub fn heap_sort<T: Ord>(arr: &mut [T]) {
         let mut heap = BinaryHeap::from_iter(arr.iter());
@@ -123,6 +312,280 @@ pub fn hash_search<T: Eq + std::hash::Hash>(
         
         hash_map.get(target).copied()
     }
+
+    /// Экспоненциальный поиск
+    ///
+    /// Удваивает правую границу, пока не перешагнет `target`, после чего
+    /// запускает бинарный поиск на найденном отрезке. Работает за O(log i),
+    /// где i — позиция элемента, поэтому выгоден для элементов в начале массива.
+    pub fn exponential_search<T: Ord>(arr: &[T], target: &T) -> Option<usize> {
+        if arr.is_empty() {
+            return None;
+        }
+
+        let mut bound = 1;
+        while bound < arr.len() && arr[bound] < *target {
+            bound *= 2;
+        }
+
+        let start = bound / 2;
+        let end = (bound + 1).min(arr.len());
+        Self::binary_search(&arr[start..end], target).map(|index| start + index)
+    }
+
+    /// Фибоначчиев поиск
+    ///
+    /// Делит отрезок в пропорции соседних чисел Фибоначчи вместо половины,
+    /// обходясь сложениями и вычитаниями вместо деления.
+    pub fn fibonacci_search<T: Ord>(arr: &[T], target: &T) -> Option<usize> {
+        let len = arr.len();
+
+        // fib = fib_1 + fib_2 — наименьшее число Фибоначчи, не меньшее len
+        let (mut fib_2, mut fib_1) = (0usize, 1usize);
+        let mut fib = fib_2 + fib_1;
+        while fib < len {
+            fib_2 = fib_1;
+            fib_1 = fib;
+            fib = fib_1 + fib_2;
+        }
+
+        // Количество элементов слева, уже исключенных из поиска
+        let mut eliminated = 0;
+        while fib > 1 {
+            let i = (eliminated + fib_2).min(len) - 1;
+            match arr[i].cmp(target) {
+                Ordering::Less => {
+                    fib = fib_1;
+                    fib_1 = fib_2;
+                    fib_2 = fib - fib_1;
+                    eliminated = i + 1;
+                }
+                Ordering::Greater => {
+                    fib = fib_2;
+                    fib_1 -= fib_2;
+                    fib_2 = fib - fib_1;
+                }
+                Ordering::Equal => return Some(i),
+            }
+        }
+
+        if fib_1 == 1 && eliminated < len && arr[eliminated] == *target {
+            Some(eliminated)
+        } else {
+            None
+        }
+    }
+}
+
+/// Операция редактирования строки
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOp {
+    /// Вставка символа
+    Insert(char),
+    /// Удаление символа
+    Delete(char),
+    /// Замена первого символа вторым
+    Replace(char, char),
+    /// Символ остается без изменений
+    Keep(char),
+}
+
+/// Расстояние Левенштейна между строками
+///
+/// Минимальное число вставок, удалений и замен символов, превращающих `a`
+/// в `b`. Из таблицы ДП хранятся только две строки: O(|a| * |b|) по времени
+/// и O(|b|) по памяти.
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let cost = usize::from(a_char != b_char);
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Последовательность операций, превращающая `a` в `b` за минимальное
+/// число правок
+///
+/// В отличие от `levenshtein_distance` хранит полную таблицу ДП, так как
+/// операции восстанавливаются обратным проходом от `(|a|, |b|)` к `(0, 0)`.
+pub fn edit_operations(a: &str, b: &str) -> Vec<EditOp> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut table = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    table[0] = (0..=b.len()).collect();
+    for (i, row) in table.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            table[i][j] = (table[i - 1][j - 1] + cost)
+                .min(table[i - 1][j] + 1)
+                .min(table[i][j - 1] + 1);
+        }
+    }
+
+    let mut operations = Vec::with_capacity(a.len().max(b.len()));
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && a[i - 1] == b[j - 1] && table[i][j] == table[i - 1][j - 1] {
+            operations.push(EditOp::Keep(a[i - 1]));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && j > 0 && table[i][j] == table[i - 1][j - 1] + 1 {
+            operations.push(EditOp::Replace(a[i - 1], b[j - 1]));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && table[i][j] == table[i - 1][j] + 1 {
+            operations.push(EditOp::Delete(a[i - 1]));
+            i -= 1;
+        } else {
+            operations.push(EditOp::Insert(b[j - 1]));
+            j -= 1;
+        }
+    }
+
+    operations.reverse();
+    operations
+}
+
+/// Наибольшая общая подпоследовательность двух срезов
+///
+/// Строит полную таблицу ДП `table[i][j]` = длина НОП для `a[..i]` и `b[..j]`
+/// и восстанавливает саму подпоследовательность обратным проходом.
+pub fn longest_common_subsequence<T: Eq + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    let mut table = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            table[i][j] = if a[i - 1] == b[j - 1] {
+                table[i - 1][j - 1] + 1
+            } else {
+                table[i - 1][j].max(table[i][j - 1])
+            };
+        }
+    }
+
+    let mut result = Vec::with_capacity(table[a.len()][b.len()]);
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            result.push(a[i - 1].clone());
+            i -= 1;
+            j -= 1;
+        } else if table[i - 1][j] >= table[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+
+    result.reverse();
+    result
+}
+
+/// Длина наибольшей общей подпоследовательности
+///
+/// Хранит только две строки таблицы ДП: O(|b|) памяти вместо O(|a| * |b|).
+pub fn lcs_length<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let mut previous = vec![0usize; b.len() + 1];
+    let mut current = vec![0usize; b.len() + 1];
+
+    for a_item in a {
+        for (j, b_item) in b.iter().enumerate() {
+            current[j + 1] = if a_item == b_item {
+                previous[j] + 1
+            } else {
+                previous[j + 1].max(current[j])
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Задача о рюкзаке 0/1
+///
+/// Возвращает максимальную суммарную ценность предметов, помещающихся в
+/// рюкзак вместимостью `capacity`, и индексы выбранных предметов по
+/// возрастанию. Значения ДП хранятся в одной строке, которая обходится
+/// справа налево, а для восстановления ответа сохраняется только битовая
+/// таблица решений "взять/не взять" размером n x (capacity + 1).
+pub fn knapsack(capacity: usize, weights: &[usize], values: &[usize]) -> (usize, Vec<usize>) {
+    assert_eq!(
+        weights.len(),
+        values.len(),
+        "количество весов и ценностей должно совпадать"
+    );
+
+    let mut best = vec![0usize; capacity + 1];
+    let mut taken = vec![vec![false; capacity + 1]; weights.len()];
+
+    for (item, (&weight, &value)) in weights.iter().zip(values).enumerate() {
+        // Обратный порядок гарантирует, что каждый предмет берется не более одного раза
+        for w in (weight..=capacity).rev() {
+            let candidate = best[w - weight] + value;
+            if candidate > best[w] {
+                best[w] = candidate;
+                taken[item][w] = true;
+            }
+        }
+    }
+
+    let mut chosen = Vec::new();
+    let mut remaining = capacity;
+    for item in (0..weights.len()).rev() {
+        if taken[item][remaining] {
+            chosen.push(item);
+            remaining -= weights[item];
+        }
+    }
+    chosen.reverse();
+
+    (best[capacity], chosen)
+}
+
+/// Минимальное остовное дерево (лес) алгоритмом Краскала
+///
+/// Ребра рассматриваются как неориентированные и перебираются по возрастанию
+/// веса; ребро попадает в остов, если соединяет разные компоненты
+/// `DisjointSet`. Для несвязного графа возвращается минимальный остовный лес.
+pub fn minimum_spanning_tree<T>(graph: &WeightedGraph<T>) -> Vec<(T, T, u64)>
+where
+    T: Hash + Eq + Clone,
+{
+    let index: HashMap<&T, usize> = graph
+        .vertices()
+        .enumerate()
+        .map(|(i, vertex)| (vertex, i))
+        .collect();
+
+    let mut edges: Vec<(&T, &T, u64)> = graph.edges().collect();
+    edges.sort_by_key(|&(_, _, weight)| weight);
+
+    let mut components = DisjointSet::new(index.len());
+    let mut tree = Vec::with_capacity(index.len().saturating_sub(1));
+    for (from, to, weight) in edges {
+        let (a, b) = (index[from], index[to]);
+        if !components.connected(a, b) {
+            components.union(a, b);
+            tree.push((from.clone(), to.clone(), weight));
+        }
+    }
+
+    tree
 }
 
 /// Демонстрация алгоритмов
@@ -137,6 +600,8 @@ pub fn demonstrate_algorithms() -> Result<(), Box<dyn std::error::Error>> {
     println!("До сортировки: {:?}", items);
     SortingAlgorithms::quick_sort(&mut items);
     println!("После быстрой сортировки: {:?}", items);
+    sort_items_by(&mut items, SortField::Id);
+    println!("После сортировки по id: {:?}", items);
 
     // Демонстрация поиска
     let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
@@ -147,12 +612,58 @@ pub fn demonstrate_algorithms() -> Result<(), Box<dyn std::error::Error>> {
     } else {
         println!("Число {} не найдено", target);
     }
+    println!(
+        "Экспоненциальный поиск: {:?}, фибоначчиев поиск: {:?}",
+        SearchingAlgorithms::exponential_search(&numbers, &target),
+        SearchingAlgorithms::fibonacci_search(&numbers, &target)
+    );
+
+    // Демонстрация расстояния редактирования
+    println!(
+        "Расстояние Левенштейна kitten -> sitting: {}",
+        levenshtein_distance("kitten", "sitting")
+    );
+    println!("Операции: {:?}", edit_operations("kitten", "sitting"));
+
+    // Демонстрация наибольшей общей подпоследовательности
+    let a: Vec<char> = "ABCBDAB".chars().collect();
+    let b: Vec<char> = "BDCAB".chars().collect();
+    let lcs: String = longest_common_subsequence(&a, &b).into_iter().collect();
+    println!("НОП ABCBDAB и BDCAB: {}", lcs);
+
+    // Демонстрация задачи о рюкзаке
+    let (value, items) = knapsack(50, &[10, 20, 30], &[60, 100, 120]);
+    println!("Рюкзак: ценность {}, предметы {:?}", value, items);
+
+    // Демонстрация минимального остовного дерева
+    let mut graph = WeightedGraph::new();
+    graph.add_edge("A", "B", 1);
+    graph.add_edge("B", "C", 2);
+    graph.add_edge("A", "C", 3);
+    println!("Минимальное остовное дерево: {:?}", minimum_spanning_tree(&graph));
 
     Ok(())
 }
 
 // Вспомогательные функции
 
+/// Перемещение медианы первого, среднего и последнего элементов в конец
+/// массива, где ее использует `partition` в качестве опорного
+fn move_median_to_end<T: Ord>(arr: &mut [T]) {
+    let last = arr.len() - 1;
+    let mid = last / 2;
+    if arr[mid] < arr[0] {
+        arr.swap(mid, 0);
+    }
+    if arr[last] < arr[0] {
+        arr.swap(last, 0);
+    }
+    // Теперь arr[0] — минимум из трех, медиана — меньший из arr[mid] и arr[last]
+    if arr[mid] < arr[last] {
+        arr.swap(mid, last);
+    }
+}
+
 fn partition<T: Ord>(arr: &mut [T]) -> usize {
     let len = arr.len();
     let pivot = len - 1;
@@ -169,6 +680,33 @@ fn partition<T: Ord>(arr: &mut [T]) -> usize {
     store_index
 }
 
+/// Устойчивая сортировка подсчетом по ключу из диапазона `0..buckets`
+fn counting_sort_by_key<F>(arr: &mut [u32], buckets: usize, key: F)
+where
+    F: Fn(u32) -> usize,
+{
+    let mut counts = vec![0usize; buckets];
+    for &value in arr.iter() {
+        counts[key(value)] += 1;
+    }
+
+    // Префиксные суммы: counts[k] становится позицией первого элемента с ключом k
+    let mut position = 0;
+    for count in counts.iter_mut() {
+        let current = *count;
+        *count = position;
+        position += current;
+    }
+
+    let mut output = vec![0u32; arr.len()];
+    for &value in arr.iter() {
+        let slot = &mut counts[key(value)];
+        output[*slot] = value;
+        *slot += 1;
+    }
+    arr.copy_from_slice(&output);
+}
+
 fn merge<T: Ord + Clone>(arr: &mut [T], left: &[T], right: &[T]) {
     let mut i = 0;
     let mut j = 0;
@@ -222,4 +760,241 @@ fn test_binary_search() {
         assert_eq!(SearchingAlgorithms::binary_search(&arr, &7), Some(6));
         assert_eq!(SearchingAlgorithms::binary_search(&arr, &11), None);
     }
+
+    #[test]
+    fn test_minimum_spanning_tree() {
+        // Полный граф на 4 вершинах, стоимость MST = 1 + 2 + 3 = 6
+        let mut graph = WeightedGraph::new();
+        graph.add_edge('A', 'B', 1);
+        graph.add_edge('A', 'C', 4);
+        graph.add_edge('A', 'D', 3);
+        graph.add_edge('B', 'C', 2);
+        graph.add_edge('B', 'D', 5);
+        graph.add_edge('C', 'D', 6);
+
+        let tree = minimum_spanning_tree(&graph);
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.iter().map(|&(_, _, weight)| weight).sum::<u64>(), 6);
+    }
+
+    fn shuffled_u32(len: usize, max_value: u32) -> Vec<u32> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        (0..len).map(|_| rng.gen_range(0..=max_value)).collect()
+    }
+
+    #[test]
+    fn test_counting_sort() {
+        let mut arr = shuffled_u32(100_000, 1_000);
+        let mut expected = arr.clone();
+        expected.sort();
+        SortingAlgorithms::counting_sort(&mut arr, 1_000);
+        assert_eq!(arr, expected);
+    }
+
+    #[test]
+    fn test_radix_sort() {
+        let mut arr = shuffled_u32(100_000, u32::MAX);
+        let mut expected = arr.clone();
+        expected.sort();
+        SortingAlgorithms::radix_sort(&mut arr);
+        assert_eq!(arr, expected);
+
+        let mut empty: Vec<u32> = Vec::new();
+        SortingAlgorithms::radix_sort(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_tim_sort() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        for len in [0, 1, 63, 64, 1000] {
+            let sorted: Vec<i32> = (0..len).collect();
+            let reversed: Vec<i32> = sorted.iter().rev().copied().collect();
+            let random: Vec<i32> = (0..len).map(|_| rng.gen_range(-50..50)).collect();
+
+            for input in [sorted, reversed, random] {
+                let mut expected = input.clone();
+                expected.sort();
+                let mut actual = input;
+                SortingAlgorithms::tim_sort(&mut actual);
+                assert_eq!(actual, expected, "длина {}", len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tim_sort_is_stable() {
+        // Сравнение только по ключу: при равных ключах порядок индексов сохраняется
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        struct Keyed(i32, usize);
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Keyed {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut items: Vec<Keyed> = (0..500).map(|i| Keyed((i * 7 % 5) as i32, i)).collect();
+        SortingAlgorithms::tim_sort(&mut items);
+        for pair in items.windows(2) {
+            assert!(pair[0].0 < pair[1].0 || pair[0].1 < pair[1].1);
+        }
+    }
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("rust", "rust"), 0);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+        assert_eq!(levenshtein_distance("abc", ""), 3);
+        assert_eq!(levenshtein_distance("ёжик", "ежик"), 1);
+    }
+
+    #[test]
+    fn test_edit_operations() {
+        let operations = edit_operations("kitten", "sitting");
+        let edits = operations
+            .iter()
+            .filter(|op| !matches!(op, EditOp::Keep(_)))
+            .count();
+        assert_eq!(edits, levenshtein_distance("kitten", "sitting"));
+
+        // Применение операций к исходной строке дает целевую
+        let mut source = "kitten".chars();
+        let mut result = String::new();
+        for op in &operations {
+            match *op {
+                EditOp::Keep(c) => {
+                    assert_eq!(source.next(), Some(c));
+                    result.push(c);
+                }
+                EditOp::Replace(from, to) => {
+                    assert_eq!(source.next(), Some(from));
+                    result.push(to);
+                }
+                EditOp::Delete(c) => assert_eq!(source.next(), Some(c)),
+                EditOp::Insert(c) => result.push(c),
+            }
+        }
+        assert_eq!(source.next(), None);
+        assert_eq!(result, "sitting");
+
+        assert_eq!(
+            edit_operations("", "ab"),
+            vec![EditOp::Insert('a'), EditOp::Insert('b')]
+        );
+        assert!(edit_operations("ab", "ab")
+            .iter()
+            .all(|op| matches!(op, EditOp::Keep(_))));
+    }
+
+    #[test]
+    fn test_longest_common_subsequence() {
+        let a: Vec<char> = "ABCBDAB".chars().collect();
+        let b: Vec<char> = "BDCAB".chars().collect();
+        let lcs = longest_common_subsequence(&a, &b);
+        assert_eq!(lcs.len(), 4);
+        assert_eq!(lcs_length(&a, &b), 4);
+
+        // Результат действительно является подпоследовательностью обоих срезов
+        let is_subsequence = |seq: &[char], of: &[char]| {
+            let mut rest = of.iter();
+            seq.iter().all(|c| rest.any(|x| x == c))
+        };
+        assert!(is_subsequence(&lcs, &a));
+        assert!(is_subsequence(&lcs, &b));
+    }
+
+    #[test]
+    fn test_lcs_identical_and_disjoint() {
+        let same = [1, 2, 3, 4];
+        assert_eq!(longest_common_subsequence(&same, &same), same.to_vec());
+        assert_eq!(lcs_length(&same, &same), 4);
+
+        let other = [5, 6, 7];
+        assert!(longest_common_subsequence(&same, &other).is_empty());
+        assert_eq!(lcs_length(&same, &other), 0);
+        assert_eq!(lcs_length::<i32>(&[], &same), 0);
+    }
+
+    #[test]
+    fn test_knapsack() {
+        let weights = [10, 20, 30];
+        let values = [60, 100, 120];
+        let (best, items) = knapsack(50, &weights, &values);
+        assert_eq!(best, 220);
+        assert_eq!(items, vec![1, 2]);
+        assert!(items.iter().map(|&i| weights[i]).sum::<usize>() <= 50);
+    }
+
+    #[test]
+    fn test_knapsack_edge_cases() {
+        assert_eq!(knapsack(10, &[], &[]), (0, vec![]));
+        assert_eq!(knapsack(5, &[6, 7, 8], &[10, 20, 30]), (0, vec![]));
+        assert_eq!(knapsack(0, &[1], &[1]), (0, vec![]));
+    }
+
+    #[test]
+    fn test_exponential_and_fibonacci_search() {
+        for len in [0usize, 1, 2, 3, 5, 8, 13, 100, 1000] {
+            let arr: Vec<i32> = (0..len as i32).map(|x| x * 2).collect();
+            for (i, value) in arr.iter().enumerate() {
+                assert_eq!(SearchingAlgorithms::exponential_search(&arr, value), Some(i));
+                assert_eq!(SearchingAlgorithms::fibonacci_search(&arr, value), Some(i));
+            }
+            for missing in [-1, 1, len as i32 * 2, len as i32 * 2 + 1] {
+                assert_eq!(SearchingAlgorithms::exponential_search(&arr, &missing), None);
+                assert_eq!(SearchingAlgorithms::fibonacci_search(&arr, &missing), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_sort() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut arr: Vec<i64> = (0..1_000_000).map(|_| rng.gen()).collect();
+        let mut expected = arr.clone();
+        expected.sort();
+        SortingAlgorithms::parallel_sort(&mut arr);
+        assert_eq!(arr, expected);
+
+        // Уже отсортированный вход не должен вырождать рекурсию
+        let mut sorted: Vec<i32> = (0..200_000).collect();
+        SortingAlgorithms::parallel_sort(&mut sorted);
+        assert!(sorted.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_sortable_item_ordering() {
+        let mut items = vec![
+            SortableItem::new(3, 2.5, "c".to_string()),
+            SortableItem::new(1, 9.0, "a".to_string()),
+            SortableItem::new(5, 0.5, "e".to_string()),
+            SortableItem::new(2, 2.5, "b".to_string()),
+            SortableItem::new(4, 7.0, "d".to_string()),
+        ];
+        let ids = |items: &[SortableItem]| items.iter().map(|item| item.id).collect::<Vec<_>>();
+
+        sort_items_by(&mut items, SortField::Id);
+        assert_eq!(ids(&items), vec![1, 2, 3, 4, 5]);
+
+        sort_items_by(&mut items, SortField::Value);
+        assert_eq!(ids(&items), vec![5, 2, 3, 4, 1]);
+
+        // Ord: по значению, при равенстве по id
+        items.reverse();
+        SortingAlgorithms::quick_sort(&mut items);
+        assert_eq!(ids(&items), vec![5, 2, 3, 4, 1]);
+        assert!(SortKey::from(&items[1]) < SortKey::from(&items[2]));
+    }
 } 
\ No newline at end of file
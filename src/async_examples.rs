@@ -5,11 +5,27 @@
 //! - Фьючеры
 //! - Стримы
 //! - Токио для асинхронного выполнения
+//! - Повторные попытки с экспоненциальной задержкой
+//! - Автоматический выключатель (circuit breaker)
+//! - Ограничение частоты запросов (token bucket)
+//! - Кооперативная отмена задач
+//! - Группировка элементов стрима в пакеты
+//! - Структурированная конкурентность с JoinSet
+//! - Трассировка через спаны `tracing`
 
-use tokio::time::{sleep, Duration};
-use tokio::stream::{self, StreamExt};
+use tokio::time::{sleep, Duration, Instant};
+use tokio_stream::{self as stream, Stream, StreamExt};
 use std::pin::Pin;
 use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use rand::Rng;
+use thiserror::Error;
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+
+/// Доля случайного отклонения задержки при включенном джиттере
+const RETRY_JITTER: f64 = 0.1;
 
 /// Асинхронная функция для демонстрации задержки
 pub async fn delay_example() {
@@ -56,21 +72,463 @@ pub fn new(value: i32) -> Self {
     }
 
     /// Асинхронный метод для обработки значения
+    #[tracing::instrument]
     pub async fn process(&self) -> i32 {
         sleep(Duration::from_secs(1)).await;
         self.value * 2
     }
 
+    /// Параллельная обработка; спаны `process` вложены в спан пакета
+    #[tracing::instrument(skip(processors), fields(count = processors.len()))]
+    pub async fn batch_process(processors: &[AsyncProcessor]) -> Vec<i32> {
+        futures::future::join_all(processors.iter().map(AsyncProcessor::process)).await
+    }
+
     /// Асинхронный метод для создания фьючера
     pub fn create_future(&self) -> Pin<Box<dyn Future<Output = i32> + '_>> {
         Box::pin(self.process())
     }
 }
 
+/// Выполнение синхронного блока внутри спана `block`
+///
+/// Имя спана в `tracing` должно быть статическим, поэтому спан всегда
+/// называется `block`, а `name` записывается в его поле `name`.
+pub fn with_span<F, T>(name: &str, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    tracing::info_span!("block", name).in_scope(f)
+}
+
+/// Настройки повторных попыток
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Максимальное число попыток, включая первую
+    pub max_attempts: u32,
+    /// Задержка перед второй попыткой
+    pub base_delay: Duration,
+    /// Верхняя граница задержки
+    pub max_delay: Duration,
+    /// Случайное отклонение задержки на ±10%
+    pub jitter: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Задержка после неудачной попытки с номером `attempt` (начиная с 1)
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        if self.jitter {
+            let scale = rand::thread_rng().gen_range(1.0 - RETRY_JITTER..=1.0 + RETRY_JITTER);
+            delay.mul_f64(scale)
+        } else {
+            delay
+        }
+    }
+}
+
+/// Повтор операции с удвоением задержки после каждой неудачи
+pub async fn retry_with_backoff<F, Fut, T, E>(f: F, max_attempts: u32, base_delay: Duration) -> Result<T, E>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let config = RetryConfig {
+        max_attempts,
+        base_delay,
+        ..RetryConfig::default()
+    };
+    retry_with_config(f, &config).await
+}
+
+/// Повтор операции по заданным настройкам; возвращает последнюю ошибку
+pub async fn retry_with_config<F, Fut, T, E>(f: F, config: &RetryConfig) -> Result<T, E>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(error) if attempt >= config.max_attempts => return Err(error),
+            Err(_) => {
+                sleep(config.delay_for(attempt)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Состояние автоматического выключателя
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Вызовы проходят, ошибки подсчитываются
+    Closed,
+    /// Вызовы отклоняются до истечения `reset_timeout`
+    Open,
+    /// Разрешен один пробный вызов
+    HalfOpen,
+}
+
+/// Ошибка вызова через автоматический выключатель
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CircuitBreakerError<E> {
+    #[error("Цепь разомкнута, вызов отклонен")]
+    CircuitOpen,
+
+    #[error("Ошибка вызова: {0}")]
+    Inner(E),
+}
+
+/// Внутреннее состояние выключателя
+#[derive(Debug)]
+struct CircuitBreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+}
+
+/// Снимает отметку пробного вызова, если его фьючер удален до завершения
+///
+/// Без этого отмененная проба (таймаут, `select!`, abort задачи) оставила бы
+/// выключатель в `HalfOpen` с `probe_in_flight`, и он бы больше не замкнулся.
+struct ProbeGuard<'a> {
+    state: &'a Mutex<CircuitBreakerState>,
+    armed: bool,
+}
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            if let Ok(mut state) = self.state.lock() {
+                state.probe_in_flight = false;
+            }
+        }
+    }
+}
+
+/// Автоматический выключатель: размыкается после `failure_threshold`
+/// ошибок подряд и перестает нагружать неисправный сервис
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    state: Mutex<CircuitBreakerState>,
+}
+
+impl CircuitBreakerState {
+    /// Переход из `Open` в `HalfOpen` по истечении таймаута
+    fn refresh(&mut self, reset_timeout: Duration) {
+        if self.state == CircuitState::Open
+            && self.opened_at.is_none_or(|at| at.elapsed() >= reset_timeout)
+        {
+            self.state = CircuitState::HalfOpen;
+            self.probe_in_flight = false;
+        }
+    }
+
+    fn open(&mut self) {
+        self.state = CircuitState::Open;
+        self.opened_at = Some(Instant::now());
+        self.probe_in_flight = false;
+    }
+}
+
+impl CircuitBreaker {
+    /// Создание выключателя в состоянии `Closed`
+    pub fn new(failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            failure_threshold,
+            reset_timeout,
+            state: Mutex::new(CircuitBreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_in_flight: false,
+            }),
+        }
+    }
+
+    /// Текущее состояние выключателя
+    pub fn state(&self) -> CircuitState {
+        let mut state = self.state.lock().unwrap();
+        state.refresh(self.reset_timeout);
+        state.state
+    }
+
+    /// Вызов операции через выключатель
+    pub async fn call<F, Fut, T, E>(&self, f: F) -> Result<T, CircuitBreakerError<E>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let is_probe = {
+            let mut state = self.state.lock().unwrap();
+            state.refresh(self.reset_timeout);
+            match state.state {
+                CircuitState::Closed => false,
+                CircuitState::Open => return Err(CircuitBreakerError::CircuitOpen),
+                CircuitState::HalfOpen if state.probe_in_flight => {
+                    return Err(CircuitBreakerError::CircuitOpen)
+                }
+                CircuitState::HalfOpen => {
+                    state.probe_in_flight = true;
+                    true
+                }
+            }
+        };
+
+        // Блокировка не удерживается во время вызова
+        let mut guard = ProbeGuard {
+            state: &self.state,
+            armed: is_probe,
+        };
+        let result = f().await;
+        // Вызов завершен: отметку снимает обработка результата ниже
+        guard.armed = false;
+
+        let mut state = self.state.lock().unwrap();
+        match result {
+            Ok(value) => {
+                state.state = CircuitState::Closed;
+                state.consecutive_failures = 0;
+                state.probe_in_flight = false;
+                Ok(value)
+            }
+            Err(error) => {
+                state.consecutive_failures += 1;
+                if state.state == CircuitState::HalfOpen
+                    || state.consecutive_failures >= self.failure_threshold
+                {
+                    state.open();
+                }
+                Err(CircuitBreakerError::Inner(error))
+            }
+        }
+    }
+}
+
+/// Запрос токенов, который никогда не будет удовлетворен
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Запрошено {requested} токенов при емкости {capacity} и скорости пополнения {refill_rate}/с")]
+pub struct RateLimited {
+    pub requested: u64,
+    pub capacity: u64,
+    pub refill_rate: u64,
+}
+
+/// Ограничитель частоты по алгоритму token bucket
+///
+/// Ведро вмещает `capacity` токенов и пополняется со скоростью
+/// `refill_rate` токенов в секунду. Подходит для ограничения входящих
+/// запросов `HttpServer`.
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: u64,
+    refill_rate: u64,
+    tokens: AtomicU64,
+    start: Instant,
+    /// Момент последнего пополнения в наносекундах от `start`
+    last_refill: AtomicU64,
+}
+
+impl TokenBucket {
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+    /// Создание заполненного ведра
+    pub fn new(capacity: u64, refill_rate: u64) -> Self {
+        Self {
+            capacity,
+            refill_rate,
+            tokens: AtomicU64::new(capacity),
+            start: Instant::now(),
+            last_refill: AtomicU64::new(0),
+        }
+    }
+
+    /// Число доступных токенов
+    pub fn available(&self) -> u64 {
+        self.refill();
+        self.tokens.load(Ordering::Acquire)
+    }
+
+    /// Получение `tokens` токенов; при нехватке ожидает пополнения
+    pub async fn acquire(&self, tokens: u64) -> Result<(), RateLimited> {
+        if tokens > self.capacity || (self.refill_rate == 0 && tokens > self.available()) {
+            return Err(self.rate_limited(tokens));
+        }
+
+        loop {
+            self.refill();
+            let taken = self
+                .tokens
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| current.checked_sub(tokens));
+            match taken {
+                Ok(_) => return Ok(()),
+                // Другая задача могла забрать токены после проверки выше,
+                // а без пополнения их уже не дождаться
+                Err(_) if self.refill_rate == 0 => return Err(self.rate_limited(tokens)),
+                Err(current) => {
+                    // Ждем ровно столько, сколько нужно для накопления недостающих токенов
+                    let missing = (tokens - current) as u128;
+                    let nanos = (missing * Self::NANOS_PER_SEC).div_ceil(self.refill_rate as u128);
+                    sleep(Duration::from_nanos(nanos as u64)).await;
+                }
+            }
+        }
+    }
+
+    /// Ошибка для запроса, который ведро не может удовлетворить
+    fn rate_limited(&self, tokens: u64) -> RateLimited {
+        RateLimited {
+            requested: tokens,
+            capacity: self.capacity,
+            refill_rate: self.refill_rate,
+        }
+    }
+
+    /// Начисление токенов за время с последнего пополнения
+    fn refill(&self) {
+        // Отметку читаем до часов: другой поток мог сдвинуть ее дальше нашего `now`,
+        // а насыщающее вычитание страхует от оставшегося зазора
+        let last = self.last_refill.load(Ordering::Acquire);
+        let now = self.start.elapsed().as_nanos() as u64;
+        let earned = now.saturating_sub(last) as u128 * self.refill_rate as u128 / Self::NANOS_PER_SEC;
+        if earned == 0 {
+            return;
+        }
+
+        // Сдвигаем отметку только на время начисленных токенов, чтобы не терять остаток
+        let spent = (earned * Self::NANOS_PER_SEC / self.refill_rate as u128) as u64;
+        if self
+            .last_refill
+            .compare_exchange(last, last + spent, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            let earned = earned.min(self.capacity as u128) as u64;
+            let _ = self.tokens.fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                Some(current.saturating_add(earned).min(self.capacity))
+            });
+        }
+    }
+}
+
+/// Токен кооперативной отмены
+///
+/// Клоны токена разделяют одно состояние: отмена через любой клон видна
+/// всем. Задачи сами проверяют токен и завершаются в удобной точке, в
+/// отличие от жесткого таймаута `with_timeout`.
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl CancellationToken {
+    /// Создание неотмененного токена
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(false);
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    /// Отмена: все ожидающие `cancelled` фьючеры завершаются
+    pub fn cancel(&self) {
+        self.sender.send_replace(true);
+    }
+
+    /// Проверка, был ли токен отменен
+    pub fn is_cancelled(&self) -> bool {
+        *self.sender.borrow()
+    }
+
+    /// Фьючер, который завершается после отмены токена
+    pub fn cancelled(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut receiver = self.sender.subscribe();
+        async move {
+            // Отправитель живет, пока жив хотя бы один клон токена
+            if receiver.wait_for(|&cancelled| cancelled).await.is_err() {
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Группировка элементов стрима в пакеты по `batch_size` штук
+///
+/// Последний пакет может быть неполным.
+pub fn batch_stream<S, T>(stream: S, batch_size: usize) -> impl Stream<Item = Vec<T>>
+where
+    S: Stream<Item = T>,
+{
+    assert!(batch_size > 0, "размер пакета должен быть положительным");
+    futures::StreamExt::chunks(stream, batch_size)
+}
+
+/// Группировка в пакеты с ограничением по времени
+///
+/// Неполный пакет отдается, если с момента получения его первого элемента
+/// прошло `timeout`.
+pub fn batch_stream_timed<S, T>(stream: S, batch_size: usize, timeout: Duration) -> impl Stream<Item = Vec<T>>
+where
+    S: Stream<Item = T>,
+{
+    assert!(batch_size > 0, "размер пакета должен быть положительным");
+    stream.chunks_timeout(batch_size, timeout)
+}
+
+/// Запуск задач в `JoinSet` со сбором результатов по мере завершения
+///
+/// Время жизни задач ограничено вызовом: если одна из них паникует,
+/// остальные отменяются и дожидаются завершения, после чего паника
+/// пробрасывается вызывающему.
+pub async fn structured_concurrency_demo(
+    tasks: Vec<impl Future<Output = String> + Send + 'static>,
+) -> Vec<String> {
+    let mut set = JoinSet::new();
+    for task in tasks {
+        set.spawn(task);
+    }
+
+    let mut results = Vec::with_capacity(set.len());
+    while let Some(joined) = set.join_next().await {
+        match joined {
+            Ok(result) => results.push(result),
+            Err(error) if error.is_panic() => {
+                set.shutdown().await;
+                std::panic::resume_unwind(error.into_panic());
+            }
+            Err(error) => unreachable!("задачи не отменяются извне: {}", error),
+        }
+    }
+    results
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tokio_test::block_on;
+    use std::sync::atomic::{AtomicU32, Ordering};
 
     #[test]
     fn test_async_processor() {
@@ -78,4 +536,330 @@ fn test_async_processor() {
         let result = block_on(processor.process());
         assert_eq!(result, 42);
     }
+
+    #[tokio::test]
+    async fn test_batch_process() {
+        let processors: Vec<_> = (1..=3).map(AsyncProcessor::new).collect();
+        let start = Instant::now();
+        assert_eq!(AsyncProcessor::batch_process(&processors).await, vec![2, 4, 6]);
+        // Обработка идет параллельно, а не по секунде на элемент
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_with_span_enters_block_span() {
+        use crate::testing::CapturedOutput;
+        use tracing_subscriber::fmt::format::FmtSpan;
+
+        let output = CapturedOutput::default();
+        let writer = output.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_span_events(FmtSpan::ENTER)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        let result = tracing::subscriber::with_default(subscriber, || {
+            with_span("сумма", || {
+                tracing::info!("внутри блока");
+                2 + 2
+            })
+        });
+        assert_eq!(result, 4);
+
+        let log = output.contents();
+        assert!(
+            log.lines().any(|line| line.contains(r#"block{name="сумма"}"#) && line.ends_with("enter")),
+            "{}",
+            log
+        );
+        assert!(
+            log.lines().any(|line| line.contains(r#"block{name="сумма"}"#) && line.ends_with("внутри блока")),
+            "{}",
+            log
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_succeeds_after_failures() {
+        let attempts = AtomicU32::new(0);
+        let result = retry_with_backoff(
+            || async {
+                // Первые две попытки завершаются ошибкой
+                if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err("временная ошибка")
+                } else {
+                    Ok(42)
+                }
+            },
+            5,
+            Duration::from_millis(1),
+        )
+        .await;
+
+        assert_eq!(result, Ok(42));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_max_attempts() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<(), u32> = retry_with_backoff(
+            || async { Err(attempts.fetch_add(1, Ordering::SeqCst)) },
+            3,
+            Duration::from_millis(1),
+        )
+        .await;
+
+        assert_eq!(result, Err(2));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let config = RetryConfig {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            jitter: false,
+        };
+        assert_eq!(config.delay_for(1), Duration::from_millis(100));
+        assert_eq!(config.delay_for(2), Duration::from_millis(200));
+        assert_eq!(config.delay_for(3), Duration::from_millis(400));
+        assert_eq!(config.delay_for(4), Duration::from_millis(500));
+
+        let config = RetryConfig { jitter: true, ..config };
+        for _ in 0..100 {
+            let delay = config.delay_for(2);
+            assert!(delay >= Duration::from_millis(180) && delay <= Duration::from_millis(220));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_transitions() {
+        let breaker = CircuitBreaker::new(2, Duration::from_millis(50));
+        let calls = AtomicU32::new(0);
+        let service = |fail: bool| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async move { if fail { Err("сбой") } else { Ok("ответ") } }
+        };
+
+        // Closed: ошибки подсчитываются, успех сбрасывает счетчик
+        assert_eq!(breaker.call(|| service(true)).await, Err(CircuitBreakerError::Inner("сбой")));
+        assert_eq!(breaker.call(|| service(false)).await, Ok("ответ"));
+        assert_eq!(breaker.call(|| service(true)).await, Err(CircuitBreakerError::Inner("сбой")));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        // Open: после двух ошибок подряд вызовы отклоняются без обращения к сервису
+        assert_eq!(breaker.call(|| service(true)).await, Err(CircuitBreakerError::Inner("сбой")));
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(breaker.call(|| service(false)).await, Err(CircuitBreakerError::CircuitOpen));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        // HalfOpen: неудачная проба снова размыкает цепь
+        sleep(Duration::from_millis(60)).await;
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert_eq!(breaker.call(|| service(true)).await, Err(CircuitBreakerError::Inner("сбой")));
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // HalfOpen: успешная проба замыкает цепь
+        sleep(Duration::from_millis(60)).await;
+        assert_eq!(breaker.call(|| service(false)).await, Ok("ответ"));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(calls.load(Ordering::SeqCst), 6);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_single_probe() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        assert!(breaker.call(|| async { Err::<(), _>("сбой") }).await.is_err());
+
+        // Пока пробный вызов не завершен, остальные отклоняются
+        let probe = breaker.call(|| async {
+            sleep(Duration::from_millis(20)).await;
+            Ok::<_, &str>(1)
+        });
+        let rejected = async {
+            sleep(Duration::from_millis(5)).await;
+            breaker.call(|| async { Ok::<_, &str>(2) }).await
+        };
+        let (probe, rejected) = tokio::join!(probe, rejected);
+        assert_eq!(probe, Ok(1));
+        assert_eq!(rejected, Err(CircuitBreakerError::CircuitOpen));
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_dropped_probe() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        assert!(breaker.call(|| async { Err::<(), _>("сбой") }).await.is_err());
+
+        // Пробный вызов отменяется таймаутом, не успев завершиться
+        let probe = breaker.call(|| async {
+            sleep(Duration::from_secs(10)).await;
+            Ok::<_, &str>(1)
+        });
+        assert!(tokio::time::timeout(Duration::from_millis(10), probe).await.is_err());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // Новая проба разрешена и замыкает выключатель
+        assert_eq!(breaker.call(|| async { Ok::<_, &str>(2) }).await, Ok(2));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_token_bucket_delays_requests() {
+        // 10 запросов с пополнением 20 токенов/с: первый проходит сразу,
+        // остальные девять ждут по 50 мс
+        let bucket = TokenBucket::new(1, 20);
+        let start = Instant::now();
+        for _ in 0..10 {
+            bucket.acquire(1).await.unwrap();
+        }
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(440), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(700), "{:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn test_token_bucket_burst_and_limits() {
+        let bucket = TokenBucket::new(5, 10);
+        let start = Instant::now();
+        bucket.acquire(3).await.unwrap();
+        bucket.acquire(2).await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(50));
+        assert_eq!(bucket.available(), 0);
+
+        assert_eq!(
+            bucket.acquire(6).await,
+            Err(RateLimited { requested: 6, capacity: 5, refill_rate: 10 })
+        );
+
+        sleep(Duration::from_millis(120)).await;
+        assert!(bucket.available() >= 1);
+    }
+
+    #[test]
+    fn test_token_bucket_concurrent_refill() {
+        // Гонка за `last_refill` не должна приводить к переполнению при вычитании
+        let bucket = Arc::new(TokenBucket::new(1_000, 1_000_000_000));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let bucket = Arc::clone(&bucket);
+                std::thread::spawn(move || {
+                    for _ in 0..10_000 {
+                        assert!(bucket.available() <= 1_000);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_bucket_without_refill() {
+        let bucket = TokenBucket::new(2, 0);
+        bucket.acquire(2).await.unwrap();
+        assert_eq!(
+            bucket.acquire(1).await,
+            Err(RateLimited { requested: 1, capacity: 2, refill_rate: 0 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_token() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+
+        let waiter = tokio::spawn(clone.cancelled());
+        sleep(Duration::from_millis(10)).await;
+        assert!(!waiter.is_finished());
+
+        token.cancel();
+        waiter.await.unwrap();
+        assert!(clone.is_cancelled());
+
+        // После отмены фьючер завершается сразу
+        clone.cancelled().await;
+    }
+
+    #[tokio::test]
+    async fn test_batch_stream() {
+        let batches: Vec<Vec<i32>> = batch_stream(stream::iter(1..=10), 3).collect().await;
+        let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![3, 3, 3, 1]);
+        assert_eq!(batches[3], vec![10]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_stream_timed_flushes_partial_batch() {
+        let (sender, receiver) = tokio::sync::mpsc::channel(16);
+        let batches = batch_stream_timed(
+            tokio_stream::wrappers::ReceiverStream::new(receiver),
+            5,
+            Duration::from_millis(20),
+        );
+        tokio::pin!(batches);
+
+        sender.send(1).await.unwrap();
+        sender.send(2).await.unwrap();
+        // Пакет не заполнен, но по таймауту отдается то, что накопилось
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+
+        for i in 3..=8 {
+            sender.send(i).await.unwrap();
+        }
+        drop(sender);
+        assert_eq!(batches.next().await, Some(vec![3, 4, 5, 6, 7]));
+        assert_eq!(batches.next().await, Some(vec![8]));
+        assert_eq!(batches.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_structured_concurrency_collects_all_results() {
+        let tasks: Vec<_> = (0..5)
+            .map(|i| {
+                let delay = rand::thread_rng().gen_range(1..30);
+                async move {
+                    sleep(Duration::from_millis(delay)).await;
+                    format!("задача {}", i)
+                }
+            })
+            .collect();
+
+        let mut results = structured_concurrency_demo(tasks).await;
+        results.sort();
+        let expected: Vec<String> = (0..5).map(|i| format!("задача {}", i)).collect();
+        assert_eq!(results, expected);
+    }
+
+    #[tokio::test]
+    async fn test_structured_concurrency_propagates_panic() {
+        use futures::FutureExt;
+        use std::panic::AssertUnwindSafe;
+
+        let guard = Arc::new(());
+        let tasks: Vec<Pin<Box<dyn Future<Output = String> + Send>>> = (0..5)
+            .map(|i| {
+                let guard = Arc::clone(&guard);
+                Box::pin(async move {
+                    if i == 2 {
+                        panic!("сбой задачи");
+                    }
+                    sleep(Duration::from_secs(60)).await;
+                    drop(guard);
+                    String::new()
+                }) as Pin<Box<dyn Future<Output = String> + Send>>
+            })
+            .collect();
+
+        let result = AssertUnwindSafe(structured_concurrency_demo(tasks)).catch_unwind().await;
+        let panic = result.unwrap_err();
+        assert_eq!(panic.downcast_ref::<&str>(), Some(&"сбой задачи"));
+
+        // Все оставшиеся задачи отменены и освободили свои ресурсы
+        assert_eq!(Arc::strong_count(&guard), 1);
+    }
 } 
\ No newline at end of file
@@ -2,17 +2,31 @@
 //! 
 //! Этот модуль показывает различные аспекты конкурентного программирования:
 //! - Потоки и мьютексы
+//! - Блокировки чтения-записи
 //! - Каналы и сообщения
 //! - Атомарные операции
 //! - Синхронизация
+//! - Барьеры
 //! - Параллельное выполнение
+//! - Пул потоков
+//! - Конвейер из потоков и каналов
+//! - Lock-free очередь (MPSC)
+//! - Широковещательные каналы
+//! - Канал состояния (watch)
+//! - Модель акторов
 
-use std::sync::{Arc, Mutex, Condvar};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Condvar, RwLock};
+use std::sync::mpsc as std_mpsc;
 use std::thread;
-use std::time::Duration;
-use tokio::sync::{mpsc, Semaphore};
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use thiserror::Error;
+use tokio::sync::{broadcast, mpsc, watch, Semaphore};
 use tokio::time::sleep;
-use std::sync::atomic::{AtomicI32, Ordering};
+use std::marker::PhantomData;
+use std::ptr;
+use std::sync::atomic::{AtomicI32, AtomicPtr, Ordering};
 
 /// Структура для демонстрации потоков
 #[derive(Debug)]
@@ -21,6 +35,12 @@ pub struct ThreadDemo {
     condition: Arc<Condvar>,
 }
 
+/// Структура для демонстрации блокировок чтения-записи
+#[derive(Debug, Clone, Default)]
+pub struct RwLockDemo {
+    data: Arc<RwLock<HashMap<String, String>>>,
+}
+
 /// Структура для демонстрации каналов
 #[derive(Debug)]
 pub struct ChannelDemo {
@@ -28,6 +48,16 @@ pub struct ChannelDemo {
     receiver: mpsc::Receiver<String>,
 }
 
+/// Структура для демонстрации канала состояния
+///
+/// В отличие от `mpsc`, где каждое сообщение забирает один получатель,
+/// `watch` хранит только последнее значение: каждый подписчик видит текущее
+/// состояние и все последующие изменения, промежуточные могут пропускаться.
+#[derive(Debug)]
+pub struct WatchDemo {
+    sender: watch::Sender<String>,
+}
+
 /// Структура для демонстрации атомарных операций
 #[derive(Debug)]
 pub struct AtomicDemo {
@@ -41,6 +71,143 @@ pub struct SyncDemo {
     data: Arc<Mutex<Vec<String>>>,
 }
 
+/// Ошибка получения разрешения семафора
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SemaphoreError {
+    #[error("Разрешение не получено за {0:?}")]
+    Timeout(Duration),
+
+    #[error("Семафор закрыт")]
+    Closed,
+}
+
+/// Барьер: блокирует потоки, пока `count` из них не дойдут до точки встречи
+#[derive(Debug)]
+pub struct Barrier {
+    state: Mutex<BarrierState>,
+    condition: Condvar,
+    count: usize,
+}
+
+/// Состояние барьера
+///
+/// Номер поколения отличает текущий цикл ожидания от следующего, поэтому
+/// барьер можно использовать повторно, а ложные пробуждения не освобождают
+/// поток раньше времени.
+#[derive(Debug)]
+struct BarrierState {
+    arrived: usize,
+    generation: usize,
+}
+
+/// Задача для пула потоков
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Пул потоков с фиксированным числом рабочих
+///
+/// Задачи передаются рабочим через общий канал `std::sync::mpsc`; приемник
+/// защищен мьютексом, поэтому каждую задачу забирает ровно один рабочий.
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    sender: Option<std_mpsc::Sender<Job>>,
+}
+
+/// Рабочий поток пула
+struct Worker {
+    id: usize,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+/// Стадия конвейера
+pub type Stage<T> = Box<dyn Fn(T) -> T + Send>;
+
+/// Многостадийный конвейер: по потоку на стадию, стадии связаны каналами
+///
+/// Элементы проходят стадии строго по порядку, поэтому порядок на выходе
+/// совпадает с порядком отправки.
+pub struct Pipeline<T> {
+    input: Option<std_mpsc::Sender<T>>,
+    output: std_mpsc::Receiver<T>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+/// Lock-free очередь с несколькими производителями и одним потребителем
+///
+/// Производители добавляют элементы одной атомарной операцией `swap` над
+/// хвостом списка, без мьютексов и циклов повтора. Потребитель читает с
+/// головы, где всегда лежит фиктивный узел.
+///
+/// Проблема ABA: в очередях на `compare_exchange` поток может прочитать
+/// указатель A, уступить процессор, а за это время узел A будет извлечен,
+/// освобожден и выделен заново по тому же адресу — CAS пройдет успешно над
+/// уже другим узлом. Здесь производители не используют CAS, а узлы
+/// освобождает только потребитель после того, как перешел через них, поэтому
+/// повторно использованный адрес не может оказаться в чужом `swap`.
+///
+/// Потребитель единственный: `dequeue` требует `&mut self`, а для работы из
+/// разных потоков очередь делится через `split` на клонируемый
+/// `QueueProducer` и единственный `QueueConsumer`.
+pub struct LockFreeQueue<T> {
+    /// Последний добавленный узел; его заменяют производители
+    tail: AtomicPtr<QueueNode<T>>,
+    /// Фиктивный узел перед первым элементом; принадлежит потребителю
+    head: AtomicPtr<QueueNode<T>>,
+}
+
+/// Производитель lock-free очереди; клоны добавляют в одну очередь
+pub struct QueueProducer<T> {
+    queue: Arc<LockFreeQueue<T>>,
+}
+
+/// Единственный потребитель lock-free очереди
+pub struct QueueConsumer<T> {
+    queue: Arc<LockFreeQueue<T>>,
+}
+
+/// Узел lock-free очереди
+struct QueueNode<T> {
+    value: Option<T>,
+    next: AtomicPtr<QueueNode<T>>,
+}
+
+// Значения передаются между потоками, а указатели защищены протоколом очереди
+unsafe impl<T: Send> Send for LockFreeQueue<T> {}
+unsafe impl<T: Send> Sync for LockFreeQueue<T> {}
+
+/// Размер почтового ящика актора
+const ACTOR_MAILBOX_CAPACITY: usize = 32;
+
+/// Обработчик сообщений актора
+#[async_trait]
+pub trait Handler<M>: Send + 'static {
+    /// Обработка одного сообщения
+    async fn handle(&mut self, msg: M);
+}
+
+/// Дескриптор актора для отправки ему сообщений
+#[derive(Debug)]
+pub struct ActorHandle<M> {
+    sender: mpsc::Sender<M>,
+}
+
+/// Широковещательный канал: каждое сообщение получают все подписчики
+#[derive(Debug)]
+pub struct BroadcastChannel<T: Clone> {
+    _marker: PhantomData<T>,
+}
+
+/// Отправляющая сторона широковещательного канала
+#[derive(Debug, Clone)]
+pub struct BroadcastSender<T: Clone> {
+    sender: broadcast::Sender<T>,
+}
+
+/// Подписчик широковещательного канала
+#[derive(Debug)]
+pub struct BroadcastReceiver<T: Clone> {
+    receiver: broadcast::Receiver<T>,
+}
+
 impl ThreadDemo {
     /// Создание нового экземпляра
     pub fn new() -> Self {
@@ -79,6 +246,98 @@ pub fn demonstrate_threads(&self) {
         increment_thread.join().unwrap();
         wait_thread.join().unwrap();
     }
+
+    /// Увеличение счетчика из `n` потоков по `increments_per_thread` раз
+    ///
+    /// Возвращает значение счетчика после завершения всех потоков.
+    pub fn run_with_n_threads(&self, n: usize, increments_per_thread: usize) -> i32 {
+        let handles: Vec<_> = (0..n)
+            .map(|_| {
+                let counter = Arc::clone(&self.counter);
+                thread::spawn(move || {
+                    for _ in 0..increments_per_thread {
+                        *counter.lock().unwrap() += 1;
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        *self.counter.lock().unwrap()
+    }
+}
+
+impl RwLockDemo {
+    /// Создание нового экземпляра
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Чтение значения под разделяемой блокировкой
+    pub fn read_value(&self, key: &str) -> Option<String> {
+        self.data.read().unwrap().get(key).cloned()
+    }
+
+    /// Запись значения под эксклюзивной блокировкой
+    pub fn write_value(&self, key: String, value: String) {
+        self.data.write().unwrap().insert(key, value);
+    }
+
+    /// Сравнение пропускной способности `RwLock` и `Mutex`
+    ///
+    /// Запускает 8 читателей и 2 писателей, каждый выполняет `operations`
+    /// операций. Возвращает время работы для `RwLock` и для `Mutex`.
+    pub fn compare_with_mutex(operations: usize) -> (Duration, Duration) {
+        const READERS: usize = 8;
+        const WRITERS: usize = 2;
+
+        let demo = Self::new();
+        let start = Instant::now();
+        let mut handles = Vec::new();
+        for _ in 0..READERS {
+            let demo = demo.clone();
+            handles.push(thread::spawn(move || {
+                for i in 0..operations {
+                    demo.read_value(&format!("key{}", i % 16));
+                }
+            }));
+        }
+        for w in 0..WRITERS {
+            let demo = demo.clone();
+            handles.push(thread::spawn(move || {
+                for i in 0..operations {
+                    demo.write_value(format!("key{}", i % 16), w.to_string());
+                }
+            }));
+        }
+        handles.into_iter().for_each(|h| h.join().unwrap());
+        let rwlock_time = start.elapsed();
+
+        let data = Arc::new(Mutex::new(HashMap::<String, String>::new()));
+        let start = Instant::now();
+        let mut handles = Vec::new();
+        for _ in 0..READERS {
+            let data = Arc::clone(&data);
+            handles.push(thread::spawn(move || {
+                for i in 0..operations {
+                    let _value = data.lock().unwrap().get(&format!("key{}", i % 16)).cloned();
+                }
+            }));
+        }
+        for w in 0..WRITERS {
+            let data = Arc::clone(&data);
+            handles.push(thread::spawn(move || {
+                for i in 0..operations {
+                    data.lock().unwrap().insert(format!("key{}", i % 16), w.to_string());
+                }
+            }));
+        }
+        handles.into_iter().for_each(|h| h.join().unwrap());
+        let mutex_time = start.elapsed();
+
+        (rwlock_time, mutex_time)
+    }
 }
 
 impl ChannelDemo {
@@ -99,6 +358,31 @@ pub async fn receive(&mut self) -> Option<String> {
     }
 }
 
+impl WatchDemo {
+    /// Создание канала с начальным состоянием
+    pub fn new(initial: String) -> Self {
+        let (sender, _) = watch::channel(initial);
+        Self { sender }
+    }
+
+    /// Публикация нового состояния для всех подписчиков
+    ///
+    /// Состояние сохраняется, даже если подписчиков пока нет.
+    pub fn broadcast_state(&self, state: String) {
+        self.sender.send_replace(state);
+    }
+
+    /// Подписка: получатель сразу видит текущее состояние
+    pub fn subscribe(&self) -> watch::Receiver<String> {
+        self.sender.subscribe()
+    }
+
+    /// Текущее состояние
+    pub fn current(&self) -> String {
+        self.sender.borrow().clone()
+    }
+}
+
 impl AtomicDemo {
     /// Создание нового экземпляра
     pub fn new() -> Self {
@@ -117,6 +401,47 @@ pub fn increment(&self) {
     pub fn get_value(&self) -> i32 {
         self.counter.load(Ordering::SeqCst)
     }
+
+    /// Атомарная замена `expected` на `new`; возвращает `true` при успехе
+    ///
+    /// Успешный CAS — это операция чтения-записи, которая публикует новое
+    /// значение. `AcqRel` было бы достаточно, чтобы синхронизировать ее с
+    /// потоками, читающими именно этот счетчик. `SeqCst` дополнительно
+    /// встраивает операцию в единый глобальный порядок всех `SeqCst`
+    /// операций: если CAS служит флагом «кто первый» наряду с другими
+    /// атомарными переменными, все потоки согласятся, в каком порядке
+    /// происходили события. При неудаче ничего не записывается и никакие
+    /// данные не публикуются, поэтому для прочитанного значения хватает
+    /// `Relaxed`.
+    pub fn compare_and_swap(&self, expected: i32, new: i32) -> bool {
+        self.counter
+            .compare_exchange(expected, new, Ordering::SeqCst, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    /// Атомарное уменьшение счетчика на `delta`
+    ///
+    /// Для простого счетчика порядок не важен — атомарность `fetch_sub`
+    /// гарантирует, что ни одно уменьшение не потеряется даже с `Relaxed`.
+    /// `AcqRel` нужен, когда по результату принимается решение о чужих
+    /// данных (как в `Arc::drop`, где последний поток освобождает память и
+    /// должен видеть все записи остальных). Здесь используется `SeqCst`,
+    /// чтобы операция была упорядочена с `increment` и `compare_and_swap`.
+    pub fn fetch_sub(&self, delta: i32) {
+        self.counter.fetch_sub(delta, Ordering::SeqCst);
+    }
+
+    /// Сброс счетчика в ноль
+    ///
+    /// Это обычная запись, а не чтение-запись, поэтому `AcqRel` для нее
+    /// недопустим: `store` принимает только `Relaxed`, `Release` или
+    /// `SeqCst`. `Release` сделал бы видимыми предшествующие записи для
+    /// потока, прочитавшего ноль с `Acquire`; `SeqCst` вдобавок не дает
+    /// сбросу переупорядочиться с последующими `SeqCst` чтениями других
+    /// переменных в этом потоке.
+    pub fn reset(&self) {
+        self.counter.store(0, Ordering::SeqCst);
+    }
 }
 
 impl SyncDemo {
@@ -136,12 +461,370 @@ pub async fn add_data(&self, item: String) {
         println!("Данные добавлены: {:?}", data);
     }
 
+    /// Добавление данных с ограничением времени ожидания разрешения
+    pub async fn try_add_data(&self, item: String, timeout: Duration) -> Result<(), SemaphoreError> {
+        let _permit = tokio::time::timeout(timeout, self.semaphore.acquire())
+            .await
+            .map_err(|_| SemaphoreError::Timeout(timeout))?
+            .map_err(|_| SemaphoreError::Closed)?;
+        self.data.lock().unwrap().push(item);
+        Ok(())
+    }
+
     /// Получение данных
     pub fn get_data(&self) -> Vec<String> {
         self.data.lock().unwrap().clone()
     }
 }
 
+impl Barrier {
+    /// Создание барьера на `count` потоков
+    pub fn new(count: usize) -> Self {
+        Self {
+            state: Mutex::new(BarrierState {
+                arrived: 0,
+                generation: 0,
+            }),
+            condition: Condvar::new(),
+            count,
+        }
+    }
+
+    /// Ожидание, пока все `count` потоков не вызовут `wait`
+    pub fn wait(&self) {
+        let mut state = self.state.lock().unwrap();
+        let generation = state.generation;
+        state.arrived += 1;
+
+        if state.arrived < self.count {
+            // Ждем, пока последний поток не откроет новое поколение
+            let _state = self
+                .condition
+                .wait_while(state, |state| state.generation == generation)
+                .unwrap();
+        } else {
+            state.arrived = 0;
+            state.generation = state.generation.wrapping_add(1);
+            self.condition.notify_all();
+        }
+    }
+}
+
+impl ThreadPool {
+    /// Создание пула из `num_workers` потоков
+    pub fn new(num_workers: usize) -> Self {
+        assert!(num_workers > 0, "пул должен содержать хотя бы один поток");
+
+        let (sender, receiver) = std_mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..num_workers)
+            .map(|id| Worker::new(id, Arc::clone(&receiver)))
+            .collect();
+
+        Self {
+            workers,
+            sender: Some(sender),
+        }
+    }
+
+    /// Постановка задачи в очередь пула
+    pub fn execute<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if let Some(sender) = &self.sender {
+            sender
+                .send(Box::new(job))
+                .expect("рабочие потоки пула завершились");
+        }
+    }
+
+    /// Завершение работы: выполняет оставшиеся в очереди задачи и
+    /// дожидается завершения всех рабочих
+    pub fn shutdown(mut self) {
+        self.join_workers();
+    }
+
+    fn join_workers(&mut self) {
+        // Закрытие канала: рабочие доберут очередь и выйдут из цикла
+        drop(self.sender.take());
+
+        for worker in &mut self.workers {
+            if let Some(handle) = worker.handle.take() {
+                if handle.join().is_err() {
+                    eprintln!("Рабочий поток {} завершился паникой", worker.id);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.join_workers();
+    }
+}
+
+impl<T: Send + 'static> Pipeline<T> {
+    /// Запуск конвейера из `stages`
+    pub fn new(stages: Vec<Stage<T>>) -> Self {
+        let (input, mut receiver) = std_mpsc::channel::<T>();
+        let workers = stages
+            .into_iter()
+            .map(|stage| {
+                let (sender, next) = std_mpsc::channel();
+                let previous = std::mem::replace(&mut receiver, next);
+                // Поток завершается, когда закрыт вход или выход стадии
+                thread::spawn(move || {
+                    for item in previous {
+                        if sender.send(stage(item)).is_err() {
+                            break;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            input: Some(input),
+            output: receiver,
+            workers,
+        }
+    }
+
+    /// Отправка элемента на первую стадию
+    pub fn send(&self, item: T) {
+        if let Some(input) = &self.input {
+            input.send(item).expect("стадия конвейера завершилась паникой");
+        }
+    }
+
+    /// Получение результата последней стадии
+    ///
+    /// Блокируется до появления результата; после `close` возвращает `None`,
+    /// когда все отправленные элементы обработаны.
+    pub fn recv(&self) -> Option<T> {
+        self.output.recv().ok()
+    }
+
+    /// Закрытие входа конвейера
+    pub fn close(&mut self) {
+        drop(self.input.take());
+    }
+}
+
+impl<T> Drop for Pipeline<T> {
+    fn drop(&mut self) {
+        drop(self.input.take());
+        for worker in self.workers.drain(..) {
+            if worker.join().is_err() {
+                eprintln!("Стадия конвейера завершилась паникой");
+            }
+        }
+    }
+}
+
+impl Worker {
+    fn new(id: usize, receiver: Arc<Mutex<std_mpsc::Receiver<Job>>>) -> Self {
+        let handle = thread::spawn(move || loop {
+            // Блокировка отпускается сразу после получения задачи,
+            // чтобы остальные рабочие могли забирать следующие
+            let message = receiver.lock().unwrap().recv();
+            match message {
+                Ok(job) => job(),
+                Err(_) => break,
+            }
+        });
+
+        Self {
+            id,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl<T> QueueNode<T> {
+    fn allocate(value: Option<T>) -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            value,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }))
+    }
+}
+
+impl<T> LockFreeQueue<T> {
+    /// Создание пустой очереди
+    pub fn new() -> Self {
+        let stub = QueueNode::allocate(None);
+        Self {
+            tail: AtomicPtr::new(stub),
+            head: AtomicPtr::new(stub),
+        }
+    }
+
+    /// Добавление элемента; может вызываться из любого числа потоков
+    pub fn enqueue(&self, item: T) {
+        let node = QueueNode::allocate(Some(item));
+        // AcqRel: публикуем новый узел и получаем предыдущий хвост
+        let prev = self.tail.swap(node, Ordering::AcqRel);
+        // SAFETY: prev не освобожден, пока его next равен null
+        unsafe { (*prev).next.store(node, Ordering::Release) };
+    }
+
+    /// Извлечение элемента
+    ///
+    /// Возвращает `None`, если очередь пуста или производитель еще не успел
+    /// связать только что добавленный узел.
+    pub fn dequeue(&mut self) -> Option<T> {
+        // SAFETY: `&mut self` исключает других потребителей
+        unsafe { self.pop() }
+    }
+
+    /// Разделение на производителя и потребителя для разных потоков
+    pub fn split(self) -> (QueueProducer<T>, QueueConsumer<T>) {
+        let queue = Arc::new(self);
+        (
+            QueueProducer {
+                queue: Arc::clone(&queue),
+            },
+            QueueConsumer { queue },
+        )
+    }
+
+    /// Извлечение элемента с головы
+    ///
+    /// # Safety
+    ///
+    /// Вызывающий гарантирует, что других потребителей одновременно нет.
+    unsafe fn pop(&self) -> Option<T> {
+        let head = self.head.load(Ordering::Relaxed);
+        let next = (*head).next.load(Ordering::Acquire);
+        if next.is_null() {
+            return None;
+        }
+        self.head.store(next, Ordering::Relaxed);
+        // Acquire выше синхронизирован с Release производителя,
+        // а старая голова больше недоступна ни одному потоку
+        let item = (*next).value.take();
+        drop(Box::from_raw(head));
+        item
+    }
+}
+
+impl<T> QueueProducer<T> {
+    /// Добавление элемента в общую очередь
+    pub fn enqueue(&self, item: T) {
+        self.queue.enqueue(item);
+    }
+}
+
+impl<T> Clone for QueueProducer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: Arc::clone(&self.queue),
+        }
+    }
+}
+
+impl<T> QueueConsumer<T> {
+    /// Извлечение элемента; см. `LockFreeQueue::dequeue`
+    pub fn dequeue(&mut self) -> Option<T> {
+        // SAFETY: QueueConsumer не клонируется, а `&mut self` исключает
+        // одновременные вызовы через один и тот же потребитель
+        unsafe { self.queue.pop() }
+    }
+}
+
+impl<T> Default for LockFreeQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LockFreeQueue<T> {
+    fn drop(&mut self) {
+        while self.dequeue().is_some() {}
+        // SAFETY: остался только фиктивный узел, других ссылок на него нет
+        unsafe { drop(Box::from_raw(*self.head.get_mut())) };
+    }
+}
+
+impl<M> Clone for ActorHandle<M> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<M: Send + 'static> ActorHandle<M> {
+    /// Отправка сообщения в почтовый ящик актора
+    pub async fn send(&self, msg: M) -> Result<(), mpsc::error::SendError<M>> {
+        self.sender.send(msg).await
+    }
+}
+
+/// Запуск актора в отдельной задаче Tokio
+///
+/// Актор обрабатывает сообщения строго по одному, поэтому его состояние не
+/// требует синхронизации. Цикл завершается, когда удалены все дескрипторы.
+pub fn spawn_actor<A, M>(mut actor: A) -> ActorHandle<M>
+where
+    A: Handler<M>,
+    M: Send + 'static,
+{
+    let (sender, mut receiver) = mpsc::channel(ACTOR_MAILBOX_CAPACITY);
+    tokio::spawn(async move {
+        while let Some(msg) = receiver.recv().await {
+            actor.handle(msg).await;
+        }
+    });
+    ActorHandle { sender }
+}
+
+impl<T: Clone> BroadcastChannel<T> {
+    /// Создание канала с буфером на `capacity` сообщений
+    ///
+    /// Возвращает отправителя и фабрику подписчиков. Подписчик получает
+    /// только сообщения, отправленные после его создания.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(capacity: usize) -> (BroadcastSender<T>, impl Fn() -> BroadcastReceiver<T>) {
+        let (sender, _) = broadcast::channel(capacity);
+        let factory_sender = sender.clone();
+        let subscribe = move || BroadcastReceiver {
+            receiver: factory_sender.subscribe(),
+        };
+        (BroadcastSender { sender }, subscribe)
+    }
+}
+
+impl<T: Clone> BroadcastSender<T> {
+    /// Отправка сообщения всем подписчикам; возвращает их число
+    pub fn send(&self, message: T) -> Result<usize, broadcast::error::SendError<T>> {
+        self.sender.send(message)
+    }
+
+    /// Количество активных подписчиков
+    pub fn receiver_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl<T: Clone> BroadcastReceiver<T> {
+    /// Получение следующего сообщения
+    ///
+    /// Если подписчик отстал больше чем на размер буфера, возвращается
+    /// `RecvError::Lagged` с числом пропущенных сообщений.
+    pub async fn recv(&mut self) -> Result<T, broadcast::error::RecvError> {
+        self.receiver.recv().await
+    }
+
+    /// Неблокирующее получение сообщения
+    pub fn try_recv(&mut self) -> Result<T, broadcast::error::TryRecvError> {
+        self.receiver.try_recv()
+    }
+}
+
 /// Демонстрация конкурентного программирования
 pub async fn demonstrate_concurrency() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n=== Демонстрация конкурентного программирования ===");
@@ -150,6 +833,7 @@ pub async fn demonstrate_concurrency() -> Result<(), Box<dyn std::error::Error>>
     println!("\n1. Демонстрация потоков:");
     let thread_demo = ThreadDemo::new();
     thread_demo.demonstrate_threads();
+    println!("8 потоков по 1000 увеличений: {}", ThreadDemo::new().run_with_n_threads(8, 1000));
 
     // Демонстрация каналов
     println!("\n2. Демонстрация каналов:");
@@ -165,6 +849,10 @@ pub async fn demonstrate_concurrency() -> Result<(), Box<dyn std::error::Error>>
     atomic_demo.increment();
     atomic_demo.increment();
     println!("Финальное значение: {}", atomic_demo.get_value());
+    atomic_demo.fetch_sub(2);
+    println!("CAS 0 -> 10: {}", atomic_demo.compare_and_swap(0, 10));
+    println!("CAS 0 -> 20: {}", atomic_demo.compare_and_swap(0, 20));
+    atomic_demo.reset();
 
     // Демонстрация синхронизации
     println!("\n4. Демонстрация синхронизации:");
@@ -172,6 +860,82 @@ pub async fn demonstrate_concurrency() -> Result<(), Box<dyn std::error::Error>>
     sync_demo.add_data("Item 1".to_string()).await;
     sync_demo.add_data("Item 2".to_string()).await;
     println!("Данные: {:?}", sync_demo.get_data());
+    let result = sync_demo.try_add_data("Item 3".to_string(), Duration::from_millis(50)).await;
+    println!("Добавление с таймаутом: {:?}", result);
+
+    let barrier = Arc::new(Barrier::new(3));
+    let handles: Vec<_> = (0..3)
+        .map(|i| {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(i * 10));
+                barrier.wait();
+                println!("Поток {} прошел барьер", i);
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // Демонстрация пула потоков
+    println!("\n5. Пул потоков:");
+    let pool = ThreadPool::new(4);
+    for i in 0..4 {
+        pool.execute(move || println!("Задача {} выполнена в {:?}", i, thread::current().id()));
+    }
+    pool.shutdown();
+
+    // Демонстрация lock-free очереди
+    println!("\n6. Lock-free очередь:");
+    let mut queue = LockFreeQueue::new();
+    for i in 1..=3 {
+        queue.enqueue(i);
+    }
+    while let Some(item) = queue.dequeue() {
+        println!("Извлечено: {}", item);
+    }
+
+    // Демонстрация широковещательного канала
+    println!("\n7. Широковещательный канал:");
+    let (sender, subscribe) = BroadcastChannel::new(8);
+    let mut receivers: Vec<_> = (0..3).map(|_| subscribe()).collect();
+    sender.send("событие".to_string())?;
+    for (i, receiver) in receivers.iter_mut().enumerate() {
+        println!("Подписчик {} получил: {}", i, receiver.recv().await?);
+    }
+
+    // Демонстрация блокировок чтения-записи
+    println!("\n8. Блокировки чтения-записи:");
+    let rwlock_demo = RwLockDemo::new();
+    rwlock_demo.write_value("язык".to_string(), "Rust".to_string());
+    println!("Прочитано под RwLock: {:?}", rwlock_demo.read_value("язык"));
+    let (rwlock_time, mutex_time) = RwLockDemo::compare_with_mutex(10_000);
+    println!("8 читателей и 2 писателя: RwLock {:?}, Mutex {:?}", rwlock_time, mutex_time);
+
+    // Демонстрация канала состояния
+    println!("\n9. Канал состояния:");
+    let watch_demo = WatchDemo::new("запуск".to_string());
+    let mut early = watch_demo.subscribe();
+    watch_demo.broadcast_state("работа".to_string());
+    let late = watch_demo.subscribe();
+    println!("Новый подписчик сразу видит: {}", *late.borrow());
+    early.changed().await?;
+    println!("Первый подписчик получил: {}", *early.borrow_and_update());
+
+    // Демонстрация конвейера
+    println!("\n10. Конвейер:");
+    let mut pipeline = Pipeline::new(vec![
+        Box::new(|x: i32| x + 1) as Stage<i32>,
+        Box::new(|x| x * 10),
+    ]);
+    for i in 0..3 {
+        pipeline.send(i);
+    }
+    pipeline.close();
+    while let Some(result) = pipeline.recv() {
+        println!("Результат конвейера: {}", result);
+    }
 
     Ok(())
 }
@@ -180,6 +944,29 @@ pub async fn demonstrate_concurrency() -> Result<(), Box<dyn std::error::Error>>
 mod tests {
     use super::*;
 
+    /// Сообщения актора-счетчика
+    enum CounterMsg {
+        Inc,
+        Get(tokio::sync::oneshot::Sender<u64>),
+    }
+
+    /// Актор-счетчик
+    struct Counter {
+        value: u64,
+    }
+
+    #[async_trait]
+    impl Handler<CounterMsg> for Counter {
+        async fn handle(&mut self, msg: CounterMsg) {
+            match msg {
+                CounterMsg::Inc => self.value += 1,
+                CounterMsg::Get(reply) => {
+                    let _ = reply.send(self.value);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_thread_demo() {
         let demo = ThreadDemo::new();
@@ -188,6 +975,13 @@ fn test_thread_demo() {
         assert_eq!(*counter, 5);
     }
 
+    #[test]
+    fn test_run_with_n_threads() {
+        let demo = ThreadDemo::new();
+        // Мьютекс сериализует все увеличения даже при 100 конкурирующих потоках
+        assert_eq!(demo.run_with_n_threads(100, 1000), 100 * 1000);
+    }
+
     #[tokio::test]
     async fn test_channel_demo() {
         let mut demo = ChannelDemo::new(1);
@@ -203,6 +997,42 @@ fn test_atomic_demo() {
         assert_eq!(demo.get_value(), 2);
     }
 
+    #[test]
+    fn test_atomic_fetch_sub_and_reset() {
+        let demo = AtomicDemo::new();
+        demo.increment();
+        demo.fetch_sub(3);
+        assert_eq!(demo.get_value(), -2);
+        demo.reset();
+        assert_eq!(demo.get_value(), 0);
+    }
+
+    #[test]
+    fn test_atomic_compare_and_swap_race() {
+        let demo = Arc::new(AtomicDemo::new());
+        let barrier = Arc::new(Barrier::new(10));
+
+        let handles: Vec<_> = (0..10)
+            .map(|_| {
+                let demo = Arc::clone(&demo);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    // Все потоки стартуют одновременно
+                    barrier.wait();
+                    demo.compare_and_swap(0, 1)
+                })
+            })
+            .collect();
+        let winners = handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .filter(|&won| won)
+            .count();
+
+        assert_eq!(winners, 1);
+        assert_eq!(demo.get_value(), 1);
+    }
+
     #[tokio::test]
     async fn test_sync_demo() {
         let demo = SyncDemo::new(2);
@@ -210,4 +1040,323 @@ async fn test_sync_demo() {
         demo.add_data("test2".to_string()).await;
         assert_eq!(demo.get_data(), vec!["test1", "test2"]);
     }
+
+    #[tokio::test]
+    async fn test_sync_try_add_data_times_out() {
+        let demo = SyncDemo::new(2);
+        demo.try_add_data("free".to_string(), Duration::from_millis(50)).await.unwrap();
+
+        // Все разрешения заняты
+        let _permits = demo.semaphore.acquire_many(2).await.unwrap();
+        let timeout = Duration::from_millis(50);
+        let start = Instant::now();
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            demo.try_add_data("blocked".to_string(), timeout),
+        )
+        .await
+        .expect("try_add_data завис");
+
+        assert_eq!(result, Err(SemaphoreError::Timeout(timeout)));
+        assert!(start.elapsed() >= timeout);
+        assert_eq!(demo.get_data(), vec!["free"]);
+    }
+
+    #[test]
+    fn test_thread_pool() {
+        let pool = ThreadPool::new(4);
+        let counter = Arc::new(AtomicI32::new(0));
+
+        for _ in 0..100 {
+            let counter = Arc::clone(&counter);
+            pool.execute(move || {
+                thread::sleep(Duration::from_micros(100));
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        pool.shutdown();
+        assert_eq!(counter.load(Ordering::SeqCst), 100);
+    }
+
+    #[test]
+    fn test_lock_free_queue_order() {
+        let mut queue = LockFreeQueue::new();
+        assert_eq!(queue.dequeue(), None);
+
+        queue.enqueue("a".to_string());
+        queue.enqueue("b".to_string());
+        assert_eq!(queue.dequeue(), Some("a".to_string()));
+        queue.enqueue("c".to_string());
+        assert_eq!(queue.dequeue(), Some("b".to_string()));
+        assert_eq!(queue.dequeue(), Some("c".to_string()));
+        assert_eq!(queue.dequeue(), None);
+
+        // Оставшиеся элементы освобождаются в Drop
+        queue.enqueue("d".to_string());
+    }
+
+    #[test]
+    fn test_lock_free_queue_stress() {
+        const THREADS: usize = 4;
+        const PER_THREAD: usize = 10_000;
+
+        let (producer, mut consumer) = LockFreeQueue::new().split();
+        let producers: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let producer = producer.clone();
+                thread::spawn(move || {
+                    for i in 0..PER_THREAD {
+                        producer.enqueue(t * PER_THREAD + i);
+                    }
+                })
+            })
+            .collect();
+
+        let mut seen = vec![false; THREADS * PER_THREAD];
+        let mut last = [None; THREADS];
+        let mut received = 0;
+        while received < THREADS * PER_THREAD {
+            if let Some(item) = consumer.dequeue() {
+                assert!(!seen[item], "элемент {} получен дважды", item);
+                seen[item] = true;
+
+                // Элементы одного производителя сохраняют порядок
+                let producer = item / PER_THREAD;
+                assert!(last[producer] < Some(item));
+                last[producer] = Some(item);
+                received += 1;
+            }
+        }
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(consumer.dequeue(), None);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_every_receiver_gets_every_message() {
+        let (sender, subscribe) = BroadcastChannel::new(16);
+        let receivers: Vec<_> = (0..3).map(|_| subscribe()).collect();
+        assert_eq!(sender.receiver_count(), 3);
+
+        for i in 0..5 {
+            assert_eq!(sender.send(i).unwrap(), 3);
+        }
+
+        for mut receiver in receivers {
+            for i in 0..5 {
+                assert_eq!(receiver.recv().await.unwrap(), i);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_slow_receiver_lags() {
+        let (sender, subscribe) = BroadcastChannel::new(2);
+        let mut fast = subscribe();
+        let mut slow = subscribe();
+
+        for i in 0..4 {
+            sender.send(i).unwrap();
+            assert_eq!(fast.recv().await.unwrap(), i);
+        }
+
+        // Отставший подписчик узнает о потере, а не пропускает сообщения молча
+        assert!(matches!(
+            slow.recv().await,
+            Err(broadcast::error::RecvError::Lagged(2))
+        ));
+        assert_eq!(slow.recv().await.unwrap(), 2);
+        assert_eq!(slow.recv().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_late_subscriber_misses_past_messages() {
+        let (sender, subscribe) = BroadcastChannel::new(4);
+        let mut early = subscribe();
+        sender.send("старое").unwrap();
+
+        let mut late = subscribe();
+        assert!(matches!(
+            late.try_recv(),
+            Err(broadcast::error::TryRecvError::Empty)
+        ));
+
+        sender.send("новое").unwrap();
+        assert_eq!(early.recv().await.unwrap(), "старое");
+        assert_eq!(early.recv().await.unwrap(), "новое");
+        assert_eq!(late.recv().await.unwrap(), "новое");
+    }
+
+    #[test]
+    fn test_rwlock_demo_read_write() {
+        let demo = RwLockDemo::new();
+        assert_eq!(demo.read_value("key"), None);
+        demo.write_value("key".to_string(), "value".to_string());
+        assert_eq!(demo.read_value("key"), Some("value".to_string()));
+    }
+
+    #[test]
+    fn test_rwlock_readers_do_not_block_each_other() {
+        let demo = RwLockDemo::new();
+        demo.write_value("key".to_string(), "value".to_string());
+        let _guard = demo.data.read().unwrap();
+
+        // Пока удерживается разделяемая блокировка, другие читатели не ждут,
+        // а писатель получить блокировку не может
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let demo = demo.clone();
+                thread::spawn(move || {
+                    let guard = demo.data.try_read().expect("чтение заблокировано");
+                    guard.get("key").cloned()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), Some("value".to_string()));
+        }
+        assert!(demo.data.try_write().is_err());
+    }
+
+    #[test]
+    fn test_rwlock_compare_with_mutex() {
+        let (rwlock_time, mutex_time) = RwLockDemo::compare_with_mutex(100);
+        assert!(rwlock_time > Duration::ZERO);
+        assert!(mutex_time > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_barrier_releases_all_threads_together() {
+        let barrier = Arc::new(Barrier::new(5));
+        let start = Instant::now();
+
+        let handles: Vec<_> = (0..5)
+            .map(|i| {
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    // Потоки приходят к барьеру в разное время
+                    thread::sleep(Duration::from_millis(i * 20));
+                    barrier.wait();
+                    Instant::now()
+                })
+            })
+            .collect();
+        let timestamps: Vec<Instant> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        let first = *timestamps.iter().min().unwrap();
+        let last = *timestamps.iter().max().unwrap();
+        assert!(first.duration_since(start) >= Duration::from_millis(80));
+        assert!(last.duration_since(first) < Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_barrier_is_reusable() {
+        let barrier = Arc::new(Barrier::new(3));
+        let counter = Arc::new(AtomicI32::new(0));
+
+        let handles: Vec<_> = (0..3)
+            .map(|_| {
+                let barrier = Arc::clone(&barrier);
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    for round in 1..=3 {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        barrier.wait();
+                        // После барьера все потоки завершили текущий раунд
+                        assert!(counter.load(Ordering::SeqCst) >= round * 3);
+                        barrier.wait();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 9);
+    }
+
+    #[tokio::test]
+    async fn test_counter_actor() {
+        let handle = spawn_actor(Counter { value: 0 });
+
+        let senders: Vec<_> = (0..4)
+            .map(|_| {
+                let handle = handle.clone();
+                tokio::spawn(async move {
+                    for _ in 0..25 {
+                        handle.send(CounterMsg::Inc).await.unwrap();
+                    }
+                })
+            })
+            .collect();
+        for sender in senders {
+            sender.await.unwrap();
+        }
+
+        let (reply, value) = tokio::sync::oneshot::channel();
+        handle.send(CounterMsg::Get(reply)).await.unwrap();
+        assert_eq!(value.await.unwrap(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_watch_all_subscribers_see_state_change() {
+        let demo = WatchDemo::new("idle".to_string());
+        let mut subscribers: Vec<_> = (0..3).map(|_| demo.subscribe()).collect();
+        for subscriber in &subscribers {
+            assert_eq!(*subscriber.borrow(), "idle");
+        }
+
+        demo.broadcast_state("running".to_string());
+        for subscriber in &mut subscribers {
+            tokio::time::timeout(Duration::from_secs(1), subscriber.changed())
+                .await
+                .expect("изменение не получено")
+                .unwrap();
+            assert_eq!(*subscriber.borrow_and_update(), "running");
+        }
+
+        // Новый подписчик сразу видит последнее состояние
+        let late = demo.subscribe();
+        assert_eq!(*late.borrow(), "running");
+        assert_eq!(demo.current(), "running");
+    }
+
+    #[test]
+    fn test_pipeline_string_stages() {
+        let mut pipeline = Pipeline::new(vec![
+            Box::new(|s: String| s.trim().to_string()) as Stage<String>,
+            Box::new(|s| s.to_uppercase()),
+            Box::new(|s| s + "!"),
+        ]);
+
+        let inputs = ["  hello ", "world  ", " rust"];
+        for input in inputs {
+            pipeline.send(input.to_string());
+        }
+        pipeline.close();
+
+        let outputs: Vec<String> = std::iter::from_fn(|| pipeline.recv()).collect();
+        assert_eq!(outputs, vec!["HELLO!", "WORLD!", "RUST!"]);
+    }
+
+    #[test]
+    fn test_pipeline_preserves_order() {
+        let pipeline = Pipeline::new(vec![
+            Box::new(|x: u32| {
+                // Неравномерная задержка не должна менять порядок
+                thread::sleep(Duration::from_micros(u64::from(x % 3) * 100));
+                x
+            }) as Stage<u32>,
+            Box::new(|x| x * 2),
+        ]);
+        for i in 0..50 {
+            pipeline.send(i);
+        }
+        let outputs: Vec<u32> = (0..50).map(|_| pipeline.recv().unwrap()).collect();
+        assert_eq!(outputs, (0..50).map(|i| i * 2).collect::<Vec<_>>());
+    }
 } 
\ No newline at end of file
@@ -3,12 +3,22 @@
 //! Этот модуль показывает различные структуры данных:
 //! - Связные списки
 //! - Деревья
-//! - Графы
+//! - Графы (в том числе взвешенные)
 //! - Хеш-таблицы
 //! - Очереди и стеки
+//! - Дерево отрезков
+//! - Система непересекающихся множеств
+//! - Список с пропусками
+//! - Арена с поколенческими индексами
+//! - Битовое множество
+//! - Фибоначчиева куча
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::hash::Hash;
+use std::iter::Rev;
+use std::ops::{Add, Range};
+use rand::Rng;
 
 /// Узел связного списка
 #[derive(Debug)]
@@ -68,6 +78,47 @@ pub fn len(&self) -> usize {
     }
 }
 
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    /// Глубокая копия с сохранением порядка элементов
+    fn clone(&self) -> Self {
+        let mut list = LinkedList::new();
+        let mut tail = &mut list.head;
+        let mut current = &self.head;
+        while let Some(node) = current {
+            let new_node = tail.insert(Box::new(Node::new(node.value.clone())));
+            tail = &mut new_node.next;
+            current = &node.next;
+        }
+        list
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    /// Поэлементное сравнение за O(n)
+    fn eq(&self, other: &Self) -> bool {
+        let mut left = &self.head;
+        let mut right = &other.head;
+        loop {
+            match (left, right) {
+                (Some(a), Some(b)) if a.value == b.value => {
+                    left = &a.next;
+                    right = &b.next;
+                }
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
 /// Реализация бинарного дерева
 #[derive(Debug)]
 pub struct TreeNode<T> {
@@ -95,6 +146,175 @@ pub fn add_left(&mut self, value: T) {
     pub fn add_right(&mut self, value: T) {
         self.right = Some(Box::new(TreeNode::new(value)));
     }
+
+    /// Симметричный обход (левое поддерево, узел, правое поддерево)
+    ///
+    /// Обходы реализованы итеративно на собственном `Stack`, чтобы глубокое
+    /// вырожденное дерево не переполнило стек вызовов.
+    pub fn inorder(&self) -> Vec<&T> {
+        let mut result = Vec::new();
+        let mut stack = Stack::new();
+        let mut current = Some(self);
+
+        loop {
+            while let Some(node) = current {
+                stack.push(node);
+                current = node.left.as_deref();
+            }
+            match stack.pop() {
+                Some(node) => {
+                    result.push(&node.value);
+                    current = node.right.as_deref();
+                }
+                None => break,
+            }
+        }
+
+        result
+    }
+
+    /// Прямой обход (узел, левое поддерево, правое поддерево)
+    pub fn preorder(&self) -> Vec<&T> {
+        let mut result = Vec::new();
+        let mut stack = Stack::new();
+        stack.push(self);
+
+        while let Some(node) = stack.pop() {
+            result.push(&node.value);
+            // Правый потомок кладется первым, чтобы левый был извлечен раньше
+            if let Some(right) = node.right.as_deref() {
+                stack.push(right);
+            }
+            if let Some(left) = node.left.as_deref() {
+                stack.push(left);
+            }
+        }
+
+        result
+    }
+
+    /// Обратный обход (левое поддерево, правое поддерево, узел)
+    pub fn postorder(&self) -> Vec<&T> {
+        let mut stack = Stack::new();
+        let mut output = Stack::new();
+        stack.push(self);
+
+        // Обход в порядке "узел, правое, левое", развернутый вторым стеком
+        while let Some(node) = stack.pop() {
+            output.push(&node.value);
+            if let Some(left) = node.left.as_deref() {
+                stack.push(left);
+            }
+            if let Some(right) = node.right.as_deref() {
+                stack.push(right);
+            }
+        }
+
+        let mut result = Vec::new();
+        while let Some(value) = output.pop() {
+            result.push(value);
+        }
+        result
+    }
+
+    /// Обход в ширину с группировкой значений по уровням глубины
+    pub fn level_order(&self) -> Vec<Vec<&T>> {
+        let mut levels: Vec<Vec<&T>> = Vec::new();
+        let mut queue = Queue::new();
+        queue.enqueue((self, 0));
+
+        while let Some((node, depth)) = queue.dequeue() {
+            if levels.len() == depth {
+                levels.push(Vec::new());
+            }
+            levels[depth].push(&node.value);
+
+            if let Some(left) = node.left.as_deref() {
+                queue.enqueue((left, depth + 1));
+            }
+            if let Some(right) = node.right.as_deref() {
+                queue.enqueue((right, depth + 1));
+            }
+        }
+
+        levels
+    }
+
+    /// Высота поддерева в ребрах (у листа высота 0)
+    pub fn height(&self) -> usize {
+        Self::levels(Some(self)) - 1
+    }
+
+    /// Проверка условия балансировки АВЛ: высоты левого и правого поддеревьев
+    /// любого узла отличаются не более чем на 1
+    pub fn is_balanced(&self) -> bool {
+        Self::balanced_levels(Some(self)).is_some()
+    }
+
+    /// Количество уровней поддерева (0 для отсутствующего узла)
+    fn levels(node: Option<&TreeNode<T>>) -> usize {
+        match node {
+            Some(node) => {
+                1 + Self::levels(node.left.as_deref()).max(Self::levels(node.right.as_deref()))
+            }
+            None => 0,
+        }
+    }
+
+    /// Количество уровней сбалансированного поддерева или `None`, если
+    /// где-то внутри нарушено условие балансировки
+    fn balanced_levels(node: Option<&TreeNode<T>>) -> Option<usize> {
+        match node {
+            Some(node) => {
+                let left = Self::balanced_levels(node.left.as_deref())?;
+                let right = Self::balanced_levels(node.right.as_deref())?;
+                if left.abs_diff(right) > 1 {
+                    return None;
+                }
+                Some(1 + left.max(right))
+            }
+            None => Some(0),
+        }
+    }
+}
+
+impl<T: Ord> TreeNode<T> {
+    /// Вставка значения с сохранением свойства дерева поиска
+    ///
+    /// Левое поддерево содержит только меньшие значения, правое — только
+    /// большие; повторная вставка существующего значения игнорируется.
+    /// Не смешивайте `bst_insert` с `add_left`/`add_right`: ручное добавление
+    /// потомков может нарушить упорядоченность, и поиск перестанет работать.
+    pub fn bst_insert(&mut self, value: T) {
+        let mut current = self;
+        loop {
+            let slot = match value.cmp(&current.value) {
+                Ordering::Less => &mut current.left,
+                Ordering::Greater => &mut current.right,
+                Ordering::Equal => return,
+            };
+            match slot {
+                Some(node) => current = node,
+                None => {
+                    *slot = Some(Box::new(TreeNode::new(value)));
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Поиск значения в дереве поиска за O(h)
+    pub fn bst_search(&self, value: &T) -> bool {
+        let mut current = Some(self);
+        while let Some(node) = current {
+            current = match value.cmp(&node.value) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
 }
 
 /// Реализация графа
@@ -104,7 +324,7 @@ pub struct Graph<T: Hash + Eq> {
     edges: HashMap<T, HashSet<T>>,
 }
 
-impl<T: Hash + Eq> Graph<T> {
+impl<T: Hash + Eq + Clone> Graph<T> {
     /// Создание нового графа
     pub fn new() -> Self {
         Self {
@@ -115,7 +335,7 @@ pub fn new() -> Self {
 
     /// Добавление вершины
     pub fn add_vertex(&mut self, vertex: T) {
-        self.vertices.insert(vertex);
+        self.vertices.insert(vertex.clone());
         self.edges.entry(vertex).or_insert_with(HashSet::new);
     }
 
@@ -130,6 +350,353 @@ pub fn add_edge(&mut self, from: T, to: T) {
     pub fn get_neighbors(&self, vertex: &T) -> Option<&HashSet<T>> {
         self.edges.get(vertex)
     }
+
+    /// Проверка наличия цикла в ориентированном графе
+    ///
+    /// Поиск в глубину с множеством вершин текущего пути: ребро в вершину,
+    /// которая еще находится на пути, означает цикл.
+    pub fn has_cycle(&self) -> bool {
+        let mut visited = HashSet::new();
+        let mut on_path = HashSet::new();
+        self.vertices
+            .iter()
+            .any(|vertex| self.find_cycle_from(vertex, &mut visited, &mut on_path))
+    }
+
+    fn find_cycle_from<'a>(
+        &'a self,
+        vertex: &'a T,
+        visited: &mut HashSet<&'a T>,
+        on_path: &mut HashSet<&'a T>,
+    ) -> bool {
+        if on_path.contains(vertex) {
+            return true;
+        }
+        if !visited.insert(vertex) {
+            return false;
+        }
+
+        on_path.insert(vertex);
+        let found = self.edges[vertex]
+            .iter()
+            .any(|neighbor| self.find_cycle_from(neighbor, visited, on_path));
+        on_path.remove(vertex);
+        found
+    }
+}
+
+impl<T: Hash + Eq> Default for Graph<T> {
+    fn default() -> Self {
+        Self {
+            vertices: HashSet::new(),
+            edges: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone + Ord> Graph<T> {
+    /// Обход в глубину из вершины `start`
+    ///
+    /// Посещаются только вершины, достижимые из `start`. Соседи перебираются
+    /// по возрастанию, чтобы порядок обхода не зависел от порядка в `HashSet`.
+    pub fn dfs(&self, start: &T) -> Vec<&T> {
+        let mut result = Vec::new();
+        let start = match self.vertices.get(start) {
+            Some(vertex) => vertex,
+            None => return result,
+        };
+
+        let mut visited = HashSet::new();
+        let mut stack = Stack::new();
+        stack.push(start);
+
+        while let Some(vertex) = stack.pop() {
+            if !visited.insert(vertex) {
+                continue;
+            }
+            result.push(vertex);
+            // В обратном порядке, чтобы меньший сосед оказался на вершине стека
+            for neighbor in self.sorted_neighbors(vertex).into_iter().rev() {
+                if !visited.contains(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Обход в ширину из вершины `start`
+    pub fn bfs(&self, start: &T) -> Vec<&T> {
+        let mut result = Vec::new();
+        let start = match self.vertices.get(start) {
+            Some(vertex) => vertex,
+            None => return result,
+        };
+
+        let mut visited = HashSet::new();
+        let mut queue = Queue::new();
+        visited.insert(start);
+        queue.enqueue(start);
+
+        while let Some(vertex) = queue.dequeue() {
+            result.push(vertex);
+            for neighbor in self.sorted_neighbors(vertex) {
+                if visited.insert(neighbor) {
+                    queue.enqueue(neighbor);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Топологическая сортировка алгоритмом Кана
+    ///
+    /// Вершины с нулевой входящей степенью выдаются по очереди, а их ребра
+    /// удаляются. Если после этого остались необработанные вершины, значит,
+    /// они лежат на цикле и упорядочить граф невозможно.
+    pub fn topological_sort(&self) -> Result<Vec<&T>, &'static str> {
+        let mut in_degree: HashMap<&T, usize> =
+            self.vertices.iter().map(|vertex| (vertex, 0)).collect();
+        for neighbors in self.edges.values() {
+            for neighbor in neighbors {
+                *in_degree.get_mut(neighbor).unwrap() += 1;
+            }
+        }
+
+        let mut sources: Vec<&T> = in_degree
+            .iter()
+            .filter(|&(_, &degree)| degree == 0)
+            .map(|(&vertex, _)| vertex)
+            .collect();
+        sources.sort();
+
+        let mut queue = Queue::new();
+        for vertex in sources {
+            queue.enqueue(vertex);
+        }
+
+        let mut order = Vec::with_capacity(self.vertices.len());
+        while let Some(vertex) = queue.dequeue() {
+            order.push(vertex);
+            for neighbor in self.sorted_neighbors(vertex) {
+                let degree = in_degree.get_mut(neighbor).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.enqueue(neighbor);
+                }
+            }
+        }
+
+        if order.len() == self.vertices.len() {
+            Ok(order)
+        } else {
+            Err("граф содержит цикл")
+        }
+    }
+
+    /// Соседи вершины в порядке возрастания
+    fn sorted_neighbors(&self, vertex: &T) -> Vec<&T> {
+        let mut neighbors: Vec<&T> = self
+            .edges
+            .get(vertex)
+            .map(|set| set.iter().collect())
+            .unwrap_or_default();
+        neighbors.sort();
+        neighbors
+    }
+}
+
+/// Реализация неориентированного графа поверх `Graph`
+///
+/// Каждое ребро хранится в обоих направлениях.
+#[derive(Debug)]
+pub struct UndirectedGraph<T: Hash + Eq> {
+    graph: Graph<T>,
+}
+
+impl<T: Hash + Eq + Clone> UndirectedGraph<T> {
+    /// Создание нового неориентированного графа
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+        }
+    }
+
+    /// Добавление вершины
+    pub fn add_vertex(&mut self, vertex: T) {
+        self.graph.add_vertex(vertex);
+    }
+
+    /// Добавление ребра между `a` и `b`
+    pub fn add_edge(&mut self, a: T, b: T) {
+        self.graph.add_edge(a.clone(), b.clone());
+        self.graph.add_edge(b, a);
+    }
+
+    /// Получение соседей вершины
+    pub fn get_neighbors(&self, vertex: &T) -> Option<&HashSet<T>> {
+        self.graph.get_neighbors(vertex)
+    }
+
+    /// Проверка наличия цикла в неориентированном графе
+    ///
+    /// Каждое ребро видно из обеих вершин, поэтому при обходе в глубину
+    /// ребро обратно в родителя не считается циклом.
+    pub fn has_cycle(&self) -> bool {
+        let mut visited = HashSet::new();
+        self.graph.vertices.iter().any(|vertex| {
+            !visited.contains(vertex) && self.find_cycle_from(vertex, None, &mut visited)
+        })
+    }
+
+    fn find_cycle_from<'a>(
+        &'a self,
+        vertex: &'a T,
+        parent: Option<&'a T>,
+        visited: &mut HashSet<&'a T>,
+    ) -> bool {
+        visited.insert(vertex);
+        for neighbor in &self.graph.edges[vertex] {
+            if !visited.contains(neighbor) {
+                if self.find_cycle_from(neighbor, Some(vertex), visited) {
+                    return true;
+                }
+            } else if Some(neighbor) != parent {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<T: Hash + Eq> Default for UndirectedGraph<T> {
+    fn default() -> Self {
+        Self {
+            graph: Graph::default(),
+        }
+    }
+}
+
+/// Реализация ориентированного взвешенного графа
+#[derive(Debug)]
+pub struct WeightedGraph<T: Hash + Eq> {
+    edges: HashMap<T, HashMap<T, u64>>,
+}
+
+/// Элемент очереди с приоритетом для алгоритма Дейкстры
+///
+/// Порядок определяется только расстоянием и инвертирован, чтобы
+/// `BinaryHeap` (max-куча) выдавала вершину с наименьшим расстоянием.
+struct DijkstraState<'a, T> {
+    distance: u64,
+    vertex: &'a T,
+}
+
+impl<T> PartialEq for DijkstraState<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance == other.distance
+    }
+}
+
+impl<T> Eq for DijkstraState<'_, T> {}
+
+impl<T> PartialOrd for DijkstraState<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for DijkstraState<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.cmp(&self.distance)
+    }
+}
+
+impl<T: Hash + Eq + Clone> WeightedGraph<T> {
+    /// Создание нового взвешенного графа
+    pub fn new() -> Self {
+        Self {
+            edges: HashMap::new(),
+        }
+    }
+
+    /// Добавление вершины
+    pub fn add_vertex(&mut self, vertex: T) {
+        self.edges.entry(vertex).or_insert_with(HashMap::new);
+    }
+
+    /// Добавление ребра с весом (повторное добавление заменяет вес)
+    pub fn add_edge(&mut self, from: T, to: T, weight: u64) {
+        self.add_vertex(to.clone());
+        self.edges.entry(from).or_insert_with(HashMap::new).insert(to, weight);
+    }
+
+    /// Получение соседей вершины вместе с весами ребер
+    pub fn get_neighbors(&self, vertex: &T) -> Option<&HashMap<T, u64>> {
+        self.edges.get(vertex)
+    }
+
+    /// Итератор по всем вершинам графа
+    pub fn vertices(&self) -> impl Iterator<Item = &T> {
+        self.edges.keys()
+    }
+
+    /// Итератор по всем ребрам графа в виде `(откуда, куда, вес)`
+    pub fn edges(&self) -> impl Iterator<Item = (&T, &T, u64)> {
+        self.edges.iter().flat_map(|(from, neighbors)| {
+            neighbors.iter().map(move |(to, &weight)| (from, to, weight))
+        })
+    }
+
+    /// Кратчайшие расстояния от `start` до всех достижимых вершин
+    ///
+    /// Алгоритм Дейкстры на двоичной куче, O((V + E) log V). Недостижимые
+    /// вершины в результат не попадают.
+    pub fn dijkstra(&self, start: &T) -> HashMap<T, u64> {
+        let mut distances: HashMap<&T, u64> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        if let Some((vertex, _)) = self.edges.get_key_value(start) {
+            distances.insert(vertex, 0);
+            heap.push(DijkstraState { distance: 0, vertex });
+        }
+
+        while let Some(DijkstraState { distance, vertex }) = heap.pop() {
+            // В куче могут остаться устаревшие записи с большим расстоянием
+            if distance > distances[vertex] {
+                continue;
+            }
+
+            for (neighbor, &weight) in &self.edges[vertex] {
+                let candidate = distance + weight;
+                let is_shorter = distances
+                    .get(neighbor)
+                    .map_or(true, |&current| candidate < current);
+                if is_shorter {
+                    distances.insert(neighbor, candidate);
+                    heap.push(DijkstraState {
+                        distance: candidate,
+                        vertex: neighbor,
+                    });
+                }
+            }
+        }
+
+        distances
+            .into_iter()
+            .map(|(vertex, distance)| (vertex.clone(), distance))
+            .collect()
+    }
+}
+
+impl<T: Hash + Eq> Default for WeightedGraph<T> {
+    fn default() -> Self {
+        Self {
+            edges: HashMap::new(),
+        }
+    }
 }
 
 /// Реализация стека
@@ -162,6 +729,46 @@ pub fn peek(&self) -> Option<&T> {
     }
 }
 
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Элементы кладутся по порядку, последний оказывается на вершине
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.data.extend(iter);
+    }
+}
+
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            data: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Обход стека от вершины ко дну
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = Rev<std::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter().rev()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Rev<std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter().rev()
+    }
+}
+
 /// Реализация очереди
 #[derive(Debug)]
 pub struct Queue<T> {
@@ -192,58 +799,929 @@ pub fn peek(&self) -> Option<&T> {
     }
 }
 
-/// Демонстрация структур данных
-pub fn demonstrate_data_structures() -> Result<(), Box<dyn std::error::Error>> {
-    // Демонстрация связного списка
-    let mut list = LinkedList::new();
-    list.push_front(1);
-    list.push_front(2);
-    list.push_front(3);
-    println!("Связный список: {:?}", list);
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    // Демонстрация бинарного дерева
-    let mut tree = TreeNode::new(1);
-    tree.add_left(2);
-    tree.add_right(3);
-    println!("Бинарное дерево: {:?}", tree);
+/// Обход очереди от начала к концу
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = std::collections::vec_deque::IntoIter<T>;
 
-    // Демонстрация графа
-    let mut graph = Graph::new();
-    graph.add_edge(1, 2);
-    graph.add_edge(2, 3);
-    graph.add_edge(1, 3);
-    println!("Граф: {:?}", graph);
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
 
-    // Демонстрация стека
-    let mut stack = Stack::new();
-    stack.push(1);
-    stack.push(2);
-    stack.push(3);
-    println!("Стек: {:?}", stack);
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = std::collections::vec_deque::Iter<'a, T>;
 
-    // Демонстрация очереди
-    let mut queue = Queue::new();
-    queue.enqueue(1);
-    queue.enqueue(2);
-    queue.enqueue(3);
-    println!("Очередь: {:?}", queue);
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
 
-    Ok(())
+/// Дерево отрезков для суммы на диапазоне и точечного обновления
+///
+/// Хранится в виде массива из `2 * n` элементов: листья лежат в `tree[n..]`,
+/// а каждый внутренний узел `i` содержит сумму узлов `2 * i` и `2 * i + 1`.
+#[derive(Debug)]
+pub struct SegmentTree<T> {
+    len: usize,
+    tree: Vec<T>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<T> SegmentTree<T>
+where
+    T: Add<Output = T> + Copy + Default,
+{
+    /// Построение дерева по исходным данным за O(n)
+    pub fn build(data: &[T]) -> Self {
+        let len = data.len();
+        let mut tree = vec![T::default(); 2 * len];
+        tree[len..].copy_from_slice(data);
+        for i in (1..len).rev() {
+            tree[i] = tree[2 * i] + tree[2 * i + 1];
+        }
+        Self { len, tree }
+    }
 
-    #[test]
-    fn test_linked_list() {
-        let mut list = LinkedList::new();
-        list.push_front(1);
-        list.push_front(2);
-        assert_eq!(list.len(), 2);
-        assert_eq!(list.pop_front(), Some(2));
-        assert_eq!(list.pop_front(), Some(1));
-        assert_eq!(list.pop_front(), None);
+    /// Количество элементов
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Проверка на пустоту
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Сумма на полуинтервале `range` за O(log n)
+    pub fn query(&self, range: Range<usize>) -> T {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "диапазон {:?} выходит за границы дерева длины {}",
+            range,
+            self.len
+        );
+
+        let mut result = T::default();
+        let mut left = range.start + self.len;
+        let mut right = range.end + self.len;
+        while left < right {
+            if left & 1 == 1 {
+                result = result + self.tree[left];
+                left += 1;
+            }
+            if right & 1 == 1 {
+                right -= 1;
+                result = result + self.tree[right];
+            }
+            left >>= 1;
+            right >>= 1;
+        }
+        result
+    }
+
+    /// Замена значения элемента с пересчетом предков за O(log n)
+    pub fn update(&mut self, index: usize, value: T) {
+        assert!(index < self.len, "индекс {} вне диапазона", index);
+
+        let mut i = index + self.len;
+        self.tree[i] = value;
+        while i > 1 {
+            i >>= 1;
+            self.tree[i] = self.tree[2 * i] + self.tree[2 * i + 1];
+        }
+    }
+}
+
+/// Система непересекающихся множеств (Union-Find)
+///
+/// Использует сжатие путей в `find` и объединение по рангу в `union`,
+/// что дает практически константное амортизированное время операций.
+#[derive(Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl DisjointSet {
+    /// Создание `n` одноэлементных множеств
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+        }
+    }
+
+    /// Поиск представителя множества со сжатием пути
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Второй проход: подвешиваем все узлы пути прямо к корню
+        let mut current = x;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Объединение множеств, содержащих `x` и `y`
+    pub fn union(&mut self, x: usize, y: usize) {
+        let root_x = self.find(x);
+        let root_y = self.find(y);
+        if root_x == root_y {
+            return;
+        }
+
+        match self.rank[root_x].cmp(&self.rank[root_y]) {
+            Ordering::Less => self.parent[root_x] = root_y,
+            Ordering::Greater => self.parent[root_y] = root_x,
+            Ordering::Equal => {
+                self.parent[root_y] = root_x;
+                self.rank[root_x] += 1;
+            }
+        }
+    }
+
+    /// Проверка, лежат ли `x` и `y` в одном множестве
+    pub fn connected(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+}
+
+/// Максимальное количество уровней списка с пропусками
+const SKIP_LIST_MAX_LEVEL: usize = 32;
+
+/// Узел списка с пропусками (головной узел не хранит значения)
+#[derive(Debug)]
+struct SkipNode<T> {
+    value: Option<T>,
+    next: Vec<Option<usize>>,
+}
+
+/// Упорядоченное множество на основе списка с пропусками
+///
+/// Узлы хранятся в арене `nodes` и ссылаются друг на друга по индексам,
+/// поэтому реализация обходится без `unsafe`. Уровень нового узла выбирается
+/// подбрасыванием монеты, что в среднем дает O(log n) на операцию.
+#[derive(Debug)]
+pub struct SkipList<T: Ord> {
+    nodes: Vec<SkipNode<T>>,
+    free: Vec<usize>,
+    level: usize,
+    len: usize,
+}
+
+impl<T: Ord> SkipList<T> {
+    /// Индекс головного узла в арене
+    const HEAD: usize = 0;
+
+    /// Создание пустого списка
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![SkipNode {
+                value: None,
+                next: vec![None; SKIP_LIST_MAX_LEVEL],
+            }],
+            free: Vec::new(),
+            level: 1,
+            len: 0,
+        }
+    }
+
+    /// Количество элементов
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Проверка на пустоту
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Вставка элемента (повторная вставка существующего значения игнорируется)
+    pub fn insert(&mut self, value: T) {
+        let update = self.predecessors(&value);
+        if self.value_after(update[0]) == Some(&value) {
+            return;
+        }
+
+        let level = Self::random_level();
+        if level > self.level {
+            self.level = level;
+        }
+
+        let node = SkipNode {
+            value: Some(value),
+            next: vec![None; level],
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        for (l, &prev) in update.iter().enumerate().take(level) {
+            self.nodes[index].next[l] = self.nodes[prev].next[l];
+            self.nodes[prev].next[l] = Some(index);
+        }
+        self.len += 1;
+    }
+
+    /// Удаление элемента; возвращает `true`, если элемент был в списке
+    pub fn remove(&mut self, value: &T) -> bool {
+        let update = self.predecessors(value);
+        let target = match self.nodes[update[0]].next[0] {
+            Some(index) if self.nodes[index].value.as_ref() == Some(value) => index,
+            _ => return false,
+        };
+
+        for (l, &prev) in update.iter().enumerate().take(self.level) {
+            if self.nodes[prev].next[l] == Some(target) {
+                self.nodes[prev].next[l] = self.nodes[target].next[l];
+            }
+        }
+
+        self.nodes[target].value = None;
+        self.nodes[target].next.clear();
+        self.free.push(target);
+
+        while self.level > 1 && self.nodes[Self::HEAD].next[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+        self.len -= 1;
+        true
+    }
+
+    /// Проверка наличия элемента
+    pub fn contains(&self, value: &T) -> bool {
+        let update = self.predecessors(value);
+        self.value_after(update[0]) == Some(value)
+    }
+
+    /// Итератор по элементам в порядке возрастания
+    pub fn iter(&self) -> SkipListIter<'_, T> {
+        SkipListIter {
+            list: self,
+            current: self.nodes[Self::HEAD].next[0],
+        }
+    }
+
+    /// Поиск последнего узла меньше `value` на каждом уровне
+    fn predecessors(&self, value: &T) -> [usize; SKIP_LIST_MAX_LEVEL] {
+        let mut update = [Self::HEAD; SKIP_LIST_MAX_LEVEL];
+        let mut current = Self::HEAD;
+        for l in (0..self.level).rev() {
+            while let Some(next) = self.nodes[current].next[l] {
+                match &self.nodes[next].value {
+                    Some(next_value) if next_value < value => current = next,
+                    _ => break,
+                }
+            }
+            update[l] = current;
+        }
+        update
+    }
+
+    /// Значение узла, следующего за `index` на нижнем уровне
+    fn value_after(&self, index: usize) -> Option<&T> {
+        self.nodes[index]
+            .next[0]
+            .and_then(|next| self.nodes[next].value.as_ref())
+    }
+
+    /// Случайный уровень нового узла: каждый следующий с вероятностью 1/2
+    fn random_level() -> usize {
+        let mut rng = rand::thread_rng();
+        let mut level = 1;
+        while level < SKIP_LIST_MAX_LEVEL && rng.gen_bool(0.5) {
+            level += 1;
+        }
+        level
+    }
+}
+
+impl<T: Ord> Default for SkipList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Итератор по списку с пропусками
+pub struct SkipListIter<'a, T: Ord> {
+    list: &'a SkipList<T>,
+    current: Option<usize>,
+}
+
+impl<'a, T: Ord> Iterator for SkipListIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.current?;
+        let node = &self.list.nodes[index];
+        self.current = node.next[0];
+        node.value.as_ref()
+    }
+}
+
+/// Индекс в `GenerationalArena`: номер слота и поколение
+///
+/// После удаления элемента поколение слота увеличивается, поэтому
+/// старый индекс не указывает на новый элемент в том же слоте.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Index {
+    slot: usize,
+    generation: u64,
+}
+
+/// Слот арены: занятый или входящий в список свободных
+#[derive(Debug)]
+enum ArenaEntry<T> {
+    Occupied { generation: u64, value: T },
+    Free { generation: u64, next_free: Option<usize> },
+}
+
+/// Арена с поколенческими индексами (slot map)
+#[derive(Debug)]
+pub struct GenerationalArena<T> {
+    entries: Vec<ArenaEntry<T>>,
+    free_head: Option<usize>,
+    len: usize,
+}
+
+impl<T> GenerationalArena<T> {
+    /// Создание пустой арены
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            free_head: None,
+            len: 0,
+        }
+    }
+
+    /// Добавление элемента, свободные слоты используются повторно
+    pub fn insert(&mut self, value: T) -> Index {
+        self.len += 1;
+        match self.free_head {
+            Some(slot) => {
+                let generation = match self.entries[slot] {
+                    ArenaEntry::Free { generation, next_free } => {
+                        self.free_head = next_free;
+                        generation
+                    }
+                    ArenaEntry::Occupied { .. } => unreachable!("занятый слот в списке свободных"),
+                };
+                self.entries[slot] = ArenaEntry::Occupied { generation, value };
+                Index { slot, generation }
+            }
+            None => {
+                self.entries.push(ArenaEntry::Occupied { generation: 0, value });
+                Index {
+                    slot: self.entries.len() - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Удаление элемента; устаревший индекс возвращает `None`
+    pub fn remove(&mut self, index: Index) -> Option<T> {
+        self.get(index)?;
+        let entry = std::mem::replace(
+            &mut self.entries[index.slot],
+            ArenaEntry::Free {
+                generation: index.generation + 1,
+                next_free: self.free_head,
+            },
+        );
+        self.free_head = Some(index.slot);
+        self.len -= 1;
+        match entry {
+            ArenaEntry::Occupied { value, .. } => Some(value),
+            ArenaEntry::Free { .. } => None,
+        }
+    }
+
+    /// Получение элемента по индексу
+    pub fn get(&self, index: Index) -> Option<&T> {
+        match self.entries.get(index.slot)? {
+            ArenaEntry::Occupied { generation, value } if *generation == index.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Получение изменяемой ссылки на элемент
+    pub fn get_mut(&mut self, index: Index) -> Option<&mut T> {
+        match self.entries.get_mut(index.slot)? {
+            ArenaEntry::Occupied { generation, value } if *generation == index.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Проверка, что индекс указывает на живой элемент
+    pub fn contains(&self, index: Index) -> bool {
+        self.get(index).is_some()
+    }
+
+    /// Количество элементов
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Пуста ли арена
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T> Default for GenerationalArena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Битовое множество: по одному биту на элемент из `0..capacity`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+    capacity: usize,
+}
+
+impl BitSet {
+    const WORD_BITS: usize = u64::BITS as usize;
+
+    /// Создание пустого множества для индексов `0..capacity`
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(Self::WORD_BITS)],
+            capacity,
+        }
+    }
+
+    /// Максимальное число элементов
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Добавление элемента; паникует, если индекс вне `0..capacity`
+    pub fn set(&mut self, index: usize) {
+        assert!(index < self.capacity, "индекс {} вне BitSet емкостью {}", index, self.capacity);
+        self.words[index / Self::WORD_BITS] |= 1 << (index % Self::WORD_BITS);
+    }
+
+    /// Удаление элемента
+    pub fn clear(&mut self, index: usize) {
+        if index < self.capacity {
+            self.words[index / Self::WORD_BITS] &= !(1 << (index % Self::WORD_BITS));
+        }
+    }
+
+    /// Проверка наличия элемента
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.capacity && self.words[index / Self::WORD_BITS] & (1 << (index % Self::WORD_BITS)) != 0
+    }
+
+    /// Объединение; емкость результата равна большей из двух
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        let (longer, shorter) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut result = longer.clone();
+        result.capacity = self.capacity.max(other.capacity);
+        for (word, other_word) in result.words.iter_mut().zip(&shorter.words) {
+            *word |= other_word;
+        }
+        result
+    }
+
+    /// Пересечение; емкость результата равна меньшей из двух
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        BitSet {
+            words: self.words.iter().zip(&other.words).map(|(a, b)| a & b).collect(),
+            capacity: self.capacity.min(other.capacity),
+        }
+    }
+
+    /// Количество элементов
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Итератор по элементам в порядке возрастания
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.capacity).filter(move |&index| self.contains(index))
+    }
+}
+
+/// Дескриптор узла `FibonacciHeap`, выданный при вставке
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle(usize);
+
+/// Узел фибоначчиевой кучи; связи хранятся как индексы в арене
+#[derive(Debug)]
+struct FibNode<T> {
+    /// `None` после извлечения узла
+    key: Option<T>,
+    parent: Option<usize>,
+    child: Option<usize>,
+    /// Соседи в кольцевом двусвязном списке
+    left: usize,
+    right: usize,
+    degree: usize,
+    marked: bool,
+}
+
+/// Фибоначчиева куча (min-heap) с `decrease_key` за амортизированное O(1)
+///
+/// Узлы хранятся в `Vec` и не переиспользуются, поэтому дескрипторы
+/// остаются уникальными на все время жизни кучи.
+#[derive(Debug)]
+pub struct FibonacciHeap<T: Ord> {
+    nodes: Vec<FibNode<T>>,
+    min: Option<usize>,
+    len: usize,
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    /// Создание пустой кучи
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            min: None,
+            len: 0,
+        }
+    }
+
+    /// Количество элементов
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Пуста ли куча
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Минимальный элемент без извлечения
+    pub fn peek(&self) -> Option<&T> {
+        self.min.map(|min| self.key(min))
+    }
+
+    /// Вставка элемента в корневой список за O(1)
+    pub fn insert(&mut self, key: T) -> NodeHandle {
+        let index = self.nodes.len();
+        self.nodes.push(FibNode {
+            key: Some(key),
+            parent: None,
+            child: None,
+            left: index,
+            right: index,
+            degree: 0,
+            marked: false,
+        });
+        self.add_root(index);
+        self.len += 1;
+        NodeHandle(index)
+    }
+
+    /// Извлечение минимума с последующим уплотнением корней
+    pub fn extract_min(&mut self) -> Option<T> {
+        let min = self.min?;
+
+        // Дети минимума становятся корнями
+        let children = match self.nodes[min].child.take() {
+            Some(child) => self.siblings(child),
+            None => Vec::new(),
+        };
+        for child in children {
+            self.unlink(child);
+            self.nodes[child].parent = None;
+            self.splice(min, child);
+        }
+
+        let next = self.nodes[min].right;
+        self.unlink(min);
+        self.len -= 1;
+        if next == min {
+            self.min = None;
+        } else {
+            self.min = Some(next);
+            self.consolidate();
+        }
+        self.nodes[min].key.take()
+    }
+
+    /// Уменьшение ключа узла с вырезанием из родителя при нарушении порядка
+    ///
+    /// Паникует, если узел уже извлечен или новый ключ больше текущего.
+    pub fn decrease_key(&mut self, handle: NodeHandle, new_key: T) {
+        let node = handle.0;
+        assert!(
+            new_key <= *self.key(node),
+            "новый ключ больше текущего"
+        );
+        self.nodes[node].key = Some(new_key);
+
+        if let Some(parent) = self.nodes[node].parent {
+            if self.key(node) < self.key(parent) {
+                self.cut(node, parent);
+                self.cascading_cut(parent);
+            }
+        }
+        if let Some(min) = self.min {
+            if self.key(node) < self.key(min) {
+                self.min = Some(node);
+            }
+        }
+    }
+
+    /// Слияние двух куч за O(1) (не считая переноса арены)
+    ///
+    /// Узлы `other` переносятся в арену `self` со сдвигом, поэтому
+    /// дескрипторы `other` перестают быть действительными. Возвращенная
+    /// функция переводит дескриптор `other` в дескриптор того же узла в `self`.
+    pub fn merge(&mut self, other: Self) -> impl Fn(NodeHandle) -> NodeHandle {
+        let offset = self.nodes.len();
+        self.nodes.extend(other.nodes.into_iter().map(|node| FibNode {
+            parent: node.parent.map(|i| i + offset),
+            child: node.child.map(|i| i + offset),
+            left: node.left + offset,
+            right: node.right + offset,
+            ..node
+        }));
+        self.len += other.len;
+
+        if let Some(other_min) = other.min.map(|i| i + offset) {
+            match self.min {
+                None => self.min = Some(other_min),
+                Some(min) => {
+                    // Склеиваем два кольцевых списка корней
+                    let min_right = self.nodes[min].right;
+                    let other_left = self.nodes[other_min].left;
+                    self.nodes[min].right = other_min;
+                    self.nodes[other_min].left = min;
+                    self.nodes[other_left].right = min_right;
+                    self.nodes[min_right].left = other_left;
+                    if self.key(other_min) < self.key(min) {
+                        self.min = Some(other_min);
+                    }
+                }
+            }
+        }
+        move |handle| NodeHandle(handle.0 + offset)
+    }
+
+    fn key(&self, node: usize) -> &T {
+        self.nodes[node].key.as_ref().expect("узел уже извлечен из кучи")
+    }
+
+    /// Все узлы кольцевого списка, начиная с `start`
+    fn siblings(&self, start: usize) -> Vec<usize> {
+        let mut result = vec![start];
+        let mut current = self.nodes[start].right;
+        while current != start {
+            result.push(current);
+            current = self.nodes[current].right;
+        }
+        result
+    }
+
+    /// Вставка одиночного узла справа от `anchor`
+    fn splice(&mut self, anchor: usize, node: usize) {
+        let right = self.nodes[anchor].right;
+        self.nodes[node].left = anchor;
+        self.nodes[node].right = right;
+        self.nodes[anchor].right = node;
+        self.nodes[right].left = node;
+    }
+
+    /// Исключение узла из его списка; узел замыкается сам на себя
+    fn unlink(&mut self, node: usize) {
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.nodes[left].right = right;
+        self.nodes[right].left = left;
+        self.nodes[node].left = node;
+        self.nodes[node].right = node;
+    }
+
+    fn add_root(&mut self, node: usize) {
+        self.nodes[node].parent = None;
+        match self.min {
+            None => {
+                self.nodes[node].left = node;
+                self.nodes[node].right = node;
+                self.min = Some(node);
+            }
+            Some(min) => {
+                self.splice(min, node);
+                if self.key(node) < self.key(min) {
+                    self.min = Some(node);
+                }
+            }
+        }
+    }
+
+    /// Подвешивание корня `child` под корень `parent`
+    fn link(&mut self, child: usize, parent: usize) {
+        self.unlink(child);
+        self.nodes[child].parent = Some(parent);
+        self.nodes[child].marked = false;
+        match self.nodes[parent].child {
+            Some(first) => self.splice(first, child),
+            None => self.nodes[parent].child = Some(child),
+        }
+        self.nodes[parent].degree += 1;
+    }
+
+    /// Объединение корней одинаковой степени
+    fn consolidate(&mut self) {
+        let roots = match self.min {
+            Some(min) => self.siblings(min),
+            None => return,
+        };
+        let mut by_degree: Vec<Option<usize>> = Vec::new();
+        for mut root in roots {
+            let mut degree = self.nodes[root].degree;
+            loop {
+                if degree >= by_degree.len() {
+                    by_degree.resize(degree + 1, None);
+                }
+                let Some(mut other) = by_degree[degree].take() else {
+                    break;
+                };
+                if self.key(other) < self.key(root) {
+                    std::mem::swap(&mut root, &mut other);
+                }
+                self.link(other, root);
+                degree += 1;
+            }
+            by_degree[degree] = Some(root);
+        }
+
+        self.min = None;
+        for root in by_degree.into_iter().flatten() {
+            self.nodes[root].left = root;
+            self.nodes[root].right = root;
+            self.add_root(root);
+        }
+    }
+
+    /// Перенос узла из детей `parent` в корневой список
+    fn cut(&mut self, node: usize, parent: usize) {
+        if self.nodes[parent].child == Some(node) {
+            let right = self.nodes[node].right;
+            self.nodes[parent].child = (right != node).then_some(right);
+        }
+        self.unlink(node);
+        self.nodes[parent].degree -= 1;
+        self.nodes[node].marked = false;
+        self.add_root(node);
+    }
+
+    /// Каскадное вырезание помеченных предков
+    fn cascading_cut(&mut self, mut node: usize) {
+        while let Some(parent) = self.nodes[node].parent {
+            if !self.nodes[node].marked {
+                self.nodes[node].marked = true;
+                break;
+            }
+            self.cut(node, parent);
+            node = parent;
+        }
+    }
+}
+
+impl<T: Ord> Default for FibonacciHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Демонстрация структур данных
+pub fn demonstrate_data_structures() -> Result<(), Box<dyn std::error::Error>> {
+    // Демонстрация связного списка
+    let mut list = LinkedList::new();
+    list.push_front(1);
+    list.push_front(2);
+    list.push_front(3);
+    println!("Связный список: {:?}", list);
+
+    // Демонстрация бинарного дерева
+    let mut tree = TreeNode::new(1);
+    tree.add_left(2);
+    tree.add_right(3);
+    println!("Бинарное дерево: {:?}", tree);
+    println!("Симметричный обход: {:?}", tree.inorder());
+    println!("Обход по уровням: {:?}", tree.level_order());
+
+    // Демонстрация графа
+    let mut graph = Graph::new();
+    graph.add_edge(1, 2);
+    graph.add_edge(2, 3);
+    graph.add_edge(1, 3);
+    println!("Граф: {:?}", graph);
+    println!("Обход в глубину: {:?}", graph.dfs(&1));
+    println!("Обход в ширину: {:?}", graph.bfs(&1));
+    println!("Граф содержит цикл: {}", graph.has_cycle());
+    println!("Топологический порядок: {:?}", graph.topological_sort());
+
+    // Демонстрация взвешенного графа
+    let mut weighted = WeightedGraph::new();
+    weighted.add_edge("A", "B", 4);
+    weighted.add_edge("A", "C", 1);
+    weighted.add_edge("C", "B", 2);
+    println!("Кратчайшие расстояния от A: {:?}", weighted.dijkstra(&"A"));
+
+    // Демонстрация стека
+    let mut stack = Stack::new();
+    stack.push(1);
+    stack.push(2);
+    stack.push(3);
+    println!("Стек: {:?}", stack);
+
+    // Демонстрация очереди
+    let mut queue = Queue::new();
+    queue.enqueue(1);
+    queue.enqueue(2);
+    queue.enqueue(3);
+    println!("Очередь: {:?}", queue);
+
+    // Демонстрация дерева отрезков
+    let mut segment_tree = SegmentTree::build(&[1, 3, 5, 7, 9]);
+    println!("Сумма на [1, 4): {}", segment_tree.query(1..4));
+    segment_tree.update(2, 10);
+    println!("Сумма на [1, 4) после обновления: {}", segment_tree.query(1..4));
+
+    // Демонстрация системы непересекающихся множеств
+    let mut sets = DisjointSet::new(5);
+    sets.union(0, 1);
+    sets.union(3, 4);
+    println!("0 и 1 связаны: {}", sets.connected(0, 1));
+    println!("1 и 3 связаны: {}", sets.connected(1, 3));
+
+    // Демонстрация списка с пропусками
+    let mut skip_list = SkipList::new();
+    for value in [5, 1, 4, 2, 3] {
+        skip_list.insert(value);
+    }
+    println!("Список с пропусками: {:?}", skip_list.iter().collect::<Vec<_>>());
+
+    // Демонстрация арены с поколенческими индексами
+    let mut arena = GenerationalArena::new();
+    let first = arena.insert("первый");
+    arena.remove(first);
+    let second = arena.insert("второй");
+    println!("Старый индекс: {:?}, новый: {:?}", arena.get(first), arena.get(second));
+
+    // Демонстрация битового множества
+    let mut evens = BitSet::new(16);
+    let mut triples = BitSet::new(16);
+    (0..16).step_by(2).for_each(|i| evens.set(i));
+    (0..16).step_by(3).for_each(|i| triples.set(i));
+    println!("Кратные 6: {:?}", evens.intersection(&triples).iter().collect::<Vec<_>>());
+
+    // Демонстрация фибоначчиевой кучи
+    let mut fib_heap = FibonacciHeap::new();
+    fib_heap.insert(7);
+    let handle = fib_heap.insert(9);
+    fib_heap.insert(3);
+    fib_heap.decrease_key(handle, 1);
+    println!("Минимум после decrease_key: {:?}", fib_heap.extract_min());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linked_list() {
+        let mut list = LinkedList::new();
+        list.push_front(1);
+        list.push_front(2);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), None);
     }
 
     #[test]
@@ -267,4 +1745,545 @@ fn test_queue() {
         assert_eq!(queue.dequeue(), Some(2));
         assert_eq!(queue.dequeue(), None);
     }
+
+    #[test]
+    fn test_segment_tree_full_range() {
+        let data = vec![5, -2, 8, 1, 0, 7, 3];
+        let tree = SegmentTree::build(&data);
+        assert_eq!(tree.query(0..data.len()), data.iter().sum::<i32>());
+    }
+
+    #[test]
+    fn test_segment_tree_update() {
+        let mut tree = SegmentTree::build(&[1, 2, 3, 4, 5]);
+        tree.update(2, 10);
+        assert_eq!(tree.query(0..5), 22);
+        assert_eq!(tree.query(2..4), 14);
+        assert_eq!(tree.query(0..2), 3);
+    }
+
+    #[test]
+    fn test_segment_tree_single_element() {
+        let data = [4u64, 8, 15, 16, 23, 42];
+        let tree = SegmentTree::build(&data);
+        for (i, &value) in data.iter().enumerate() {
+            assert_eq!(tree.query(i..i + 1), value);
+        }
+        assert_eq!(tree.query(3..3), 0);
+    }
+
+    #[test]
+    fn test_disjoint_set_cycle() {
+        let mut sets = DisjointSet::new(4);
+        sets.union(0, 1);
+        sets.union(1, 2);
+        sets.union(2, 3);
+        // Ребро 3-0 замыкает цикл: вершины уже в одном множестве
+        assert!(sets.connected(3, 0));
+        sets.union(3, 0);
+        assert!((0..4).all(|v| sets.connected(0, v)));
+    }
+
+    #[test]
+    fn test_disjoint_set_separate_components() {
+        let mut sets = DisjointSet::new(6);
+        sets.union(0, 1);
+        sets.union(1, 2);
+        sets.union(3, 4);
+        sets.union(4, 5);
+        assert!(sets.connected(0, 2));
+        assert!(sets.connected(3, 5));
+        assert!(!sets.connected(0, 3));
+        assert!(!sets.connected(2, 5));
+    }
+
+    #[test]
+    fn test_disjoint_set_path_compression() {
+        let mut sets = DisjointSet::new(4);
+        // Строим цепочку 3 -> 2 -> 1 -> 0 в обход union
+        sets.parent = vec![0, 0, 1, 2];
+        let root = sets.find(3);
+        assert_eq!(root, 0);
+        assert_eq!(sets.parent, vec![0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_skip_list() {
+        let mut rng = rand::thread_rng();
+        let values: Vec<i32> = (0..10_000).map(|_| rng.gen_range(-100_000..100_000)).collect();
+
+        let mut list = SkipList::new();
+        for &value in &values {
+            list.insert(value);
+        }
+
+        let mut expected = values.clone();
+        expected.sort();
+        expected.dedup();
+        assert_eq!(list.len(), expected.len());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+
+        let (removed, kept) = expected.split_at(expected.len() / 2);
+        for value in removed {
+            assert!(list.remove(value));
+        }
+        for value in removed {
+            assert!(!list.contains(value));
+            assert!(!list.remove(value));
+        }
+        for value in kept {
+            assert!(list.contains(value));
+        }
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), kept);
+    }
+
+    /// Полное бинарное дерево из 7 узлов:
+    ///
+    /// ```text
+    ///        1
+    ///      /   \
+    ///     2     3
+    ///    / \   / \
+    ///   4   5 6   7
+    /// ```
+    fn complete_tree() -> TreeNode<i32> {
+        let mut root = TreeNode::new(1);
+        root.add_left(2);
+        root.add_right(3);
+        let left = root.left.as_mut().unwrap();
+        left.add_left(4);
+        left.add_right(5);
+        let right = root.right.as_mut().unwrap();
+        right.add_left(6);
+        right.add_right(7);
+        root
+    }
+
+    #[test]
+    fn test_tree_traversals() {
+        let tree = complete_tree();
+        assert_eq!(tree.inorder(), vec![&4, &2, &5, &1, &6, &3, &7]);
+        assert_eq!(tree.preorder(), vec![&1, &2, &4, &5, &3, &6, &7]);
+        assert_eq!(tree.postorder(), vec![&4, &5, &2, &6, &7, &3, &1]);
+    }
+
+    #[test]
+    fn test_tree_level_order() {
+        let tree = complete_tree();
+        let levels = tree.level_order();
+        let sizes: Vec<usize> = levels.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![1, 2, 4]);
+        assert_eq!(levels[0], vec![&1]);
+        assert_eq!(levels[1], vec![&2, &3]);
+        assert_eq!(levels[2], vec![&4, &5, &6, &7]);
+    }
+
+    #[test]
+    fn test_tree_height_and_balance() {
+        let leaf = TreeNode::new(0);
+        assert_eq!(leaf.height(), 0);
+        assert!(leaf.is_balanced());
+
+        let tree = complete_tree();
+        assert_eq!(tree.height(), 2);
+        assert!(tree.is_balanced());
+
+        // Вырожденная цепочка 1 -> 2 -> 3, растущая вправо
+        let mut chain = TreeNode::new(1);
+        chain.add_right(2);
+        chain.right.as_mut().unwrap().add_right(3);
+        assert_eq!(chain.height(), 2);
+        assert!(!chain.is_balanced());
+    }
+
+    #[test]
+    fn test_bst_insert_and_search() {
+        use rand::seq::SliceRandom;
+
+        let mut values: Vec<i32> = (0..200).map(|x| x * 2).collect();
+        values.shuffle(&mut rand::thread_rng());
+
+        let mut tree = TreeNode::new(values[0]);
+        for &value in &values[1..] {
+            tree.bst_insert(value);
+        }
+
+        for value in &values {
+            assert!(tree.bst_search(value));
+        }
+        for missing in [-1, 1, 3, 199, 400] {
+            assert!(!tree.bst_search(&missing));
+        }
+
+        // Симметричный обход дерева поиска выдает отсортированную последовательность
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert_eq!(tree.inorder().into_iter().copied().collect::<Vec<_>>(), sorted);
+    }
+
+    /// Ориентированный граф из 6 вершин с обратным ребром 5 -> 2
+    fn traversal_graph() -> Graph<i32> {
+        let mut graph = Graph::new();
+        graph.add_edge(1, 3);
+        graph.add_edge(1, 2);
+        graph.add_edge(3, 6);
+        graph.add_edge(2, 4);
+        graph.add_edge(4, 5);
+        graph.add_edge(5, 2);
+        graph.add_edge(6, 5);
+        graph
+    }
+
+    #[test]
+    fn test_graph_dfs() {
+        let graph = traversal_graph();
+        assert_eq!(graph.dfs(&1), vec![&1, &2, &4, &5, &3, &6]);
+        assert_eq!(graph.dfs(&3), vec![&3, &6, &5, &2, &4]);
+    }
+
+    #[test]
+    fn test_graph_bfs() {
+        let graph = traversal_graph();
+        assert_eq!(graph.bfs(&1), vec![&1, &2, &3, &4, &6, &5]);
+        assert_eq!(graph.bfs(&3), vec![&3, &6, &5, &2, &4]);
+    }
+
+    #[test]
+    fn test_graph_traversal_disconnected() {
+        let mut graph = traversal_graph();
+        graph.add_vertex(7);
+        graph.add_edge(8, 1);
+        assert_eq!(graph.dfs(&1).len(), 6);
+        assert_eq!(graph.bfs(&1).len(), 6);
+        assert_eq!(graph.dfs(&7), vec![&7]);
+        assert!(graph.bfs(&42).is_empty());
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Классический пример из CLRS (рис. 24.6)
+        let mut graph = WeightedGraph::new();
+        graph.add_edge("s", "t", 10);
+        graph.add_edge("s", "y", 5);
+        graph.add_edge("t", "x", 1);
+        graph.add_edge("t", "y", 2);
+        graph.add_edge("y", "t", 3);
+        graph.add_edge("y", "x", 9);
+        graph.add_edge("y", "z", 2);
+        graph.add_edge("x", "z", 4);
+        graph.add_edge("z", "x", 6);
+        graph.add_edge("z", "s", 7);
+        graph.add_vertex("isolated");
+
+        let distances = graph.dijkstra(&"s");
+        let expected: HashMap<&str, u64> =
+            [("s", 0), ("t", 8), ("x", 9), ("y", 5), ("z", 7)].into_iter().collect();
+        assert_eq!(distances, expected);
+        assert!(!distances.contains_key("isolated"));
+        assert!(graph.dijkstra(&"missing").is_empty());
+    }
+
+    #[test]
+    fn test_directed_cycle_detection() {
+        let mut triangle = Graph::new();
+        triangle.add_edge(1, 2);
+        triangle.add_edge(2, 3);
+        triangle.add_edge(3, 1);
+        assert!(triangle.has_cycle());
+
+        // Ромб 1 -> {2, 3} -> 4 сходится, но цикла не образует
+        let mut dag = Graph::new();
+        dag.add_edge(1, 2);
+        dag.add_edge(1, 3);
+        dag.add_edge(2, 4);
+        dag.add_edge(3, 4);
+        assert!(!dag.has_cycle());
+
+        let mut single = Graph::new();
+        single.add_vertex(1);
+        assert!(!single.has_cycle());
+
+        single.add_edge(1, 1);
+        assert!(single.has_cycle());
+    }
+
+    #[test]
+    fn test_undirected_cycle_detection() {
+        let mut triangle = UndirectedGraph::new();
+        triangle.add_edge(1, 2);
+        triangle.add_edge(2, 3);
+        triangle.add_edge(3, 1);
+        assert!(triangle.has_cycle());
+
+        // Дерево: ребра видны в обе стороны, но цикла нет
+        let mut tree = UndirectedGraph::new();
+        tree.add_edge(1, 2);
+        tree.add_edge(1, 3);
+        tree.add_edge(3, 4);
+        assert!(tree.get_neighbors(&2).unwrap().contains(&1));
+        assert!(!tree.has_cycle());
+
+        let mut single = UndirectedGraph::new();
+        single.add_vertex(1);
+        assert!(!single.has_cycle());
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        // Ребро a -> b означает, что a собирается раньше b
+        let mut deps = Graph::new();
+        deps.add_edge("libc", "openssl-sys");
+        deps.add_edge("openssl-sys", "openssl");
+        deps.add_edge("openssl", "reqwest");
+        deps.add_edge("libc", "tokio");
+        deps.add_edge("tokio", "reqwest");
+        deps.add_edge("serde", "reqwest");
+        deps.add_edge("serde", "serde_json");
+
+        let order = deps.topological_sort().unwrap();
+        assert_eq!(
+            order,
+            vec![&"libc", &"serde", &"openssl-sys", &"tokio", &"serde_json", &"openssl", &"reqwest"]
+        );
+
+        let position = |name: &str| order.iter().position(|&&v| v == name).unwrap();
+        for (from, to) in [("libc", "tokio"), ("openssl", "reqwest"), ("serde", "serde_json")] {
+            assert!(position(from) < position(to));
+        }
+    }
+
+    #[test]
+    fn test_topological_sort_cycle() {
+        let mut graph = traversal_graph();
+        assert!(graph.topological_sort().is_err());
+
+        // Без обратного ребра 5 -> 2 граф становится ациклическим
+        graph.edges.get_mut(&5).unwrap().remove(&2);
+        assert_eq!(graph.topological_sort().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_generational_arena() {
+        let mut arena = GenerationalArena::new();
+        let a = arena.insert("a");
+        let b = arena.insert("b");
+        let c = arena.insert("c");
+
+        assert_eq!(arena.remove(b), Some("b"));
+        assert_eq!(arena.remove(b), None);
+
+        let d = arena.insert("d");
+        // Слот удаленного элемента переиспользован с новым поколением
+        assert_eq!(d.slot, b.slot);
+        assert_ne!(d.generation, b.generation);
+        assert_eq!(arena.get(b), None);
+        assert_eq!(arena.get(d), Some(&"d"));
+        assert_eq!((arena.get(a), arena.get(c)), (Some(&"a"), Some(&"c")));
+        assert_eq!(arena.len(), 3);
+
+        *arena.get_mut(d).unwrap() = "e";
+        assert_eq!(arena.get(d), Some(&"e"));
+        assert!(arena.get_mut(b).is_none());
+    }
+
+    #[test]
+    fn test_collections_default() {
+        #[derive(Default)]
+        struct Collections {
+            list: LinkedList<i32>,
+            stack: Stack<i32>,
+            queue: Queue<i32>,
+            graph: Graph<i32>,
+            skip_list: SkipList<i32>,
+        }
+
+        let mut collections = Collections::default();
+        assert_eq!(collections.list.len(), 0);
+        assert_eq!(collections.stack.pop(), None);
+        assert_eq!(collections.queue.dequeue(), None);
+        assert!(collections.graph.vertices.is_empty());
+        assert_eq!(collections.skip_list.iter().count(), 0);
+        assert!(UndirectedGraph::<i32>::default().graph.edges.is_empty());
+        assert!(WeightedGraph::<i32>::default().edges.is_empty());
+    }
+
+    #[test]
+    fn test_linked_list_clone_and_eq() {
+        let mut original = LinkedList::new();
+        for value in [3, 2, 1] {
+            original.push_front(value);
+        }
+
+        let mut copy = original.clone();
+        assert_eq!(copy, original);
+
+        copy.pop_front();
+        copy.push_front(10);
+        assert_ne!(copy, original);
+        assert_eq!(original.pop_front(), Some(1));
+
+        let mut shorter = original.clone();
+        shorter.pop_front();
+        assert_ne!(shorter, original);
+        assert_eq!(LinkedList::<i32>::new(), LinkedList::new());
+    }
+
+    #[test]
+    fn test_stack_and_queue_into_iter() {
+        let mut stack = Stack::new();
+        let mut queue = Queue::new();
+        for value in [1, 2, 3] {
+            stack.push(value);
+            queue.enqueue(value);
+        }
+
+        assert_eq!((&stack).into_iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+        let mut sum = 0;
+        for value in &queue {
+            sum += value;
+        }
+        assert_eq!(sum, 6);
+
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_stack_from_iter_and_extend() {
+        let mut stack: Stack<i32> = (1..=5).collect();
+        assert_eq!(stack.peek(), Some(&5));
+
+        stack.extend(vec![6, 7]);
+        assert_eq!(stack.pop(), Some(7));
+
+        let stack = Stack::from_iter(vec![1, 2, 3]);
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_bit_set() {
+        let mut rng = rand::thread_rng();
+        let mut first = BitSet::new(300);
+        let mut second = BitSet::new(200);
+        let mut first_naive = HashSet::new();
+        let mut second_naive = HashSet::new();
+        for _ in 0..150 {
+            let a = rng.gen_range(0..300);
+            first.set(a);
+            first_naive.insert(a);
+            // Второе множество пересекается с первым на 0..200
+            let b = rng.gen_range(0..200);
+            second.set(b);
+            second_naive.insert(b);
+        }
+
+        assert_eq!(first.count_ones(), first_naive.len());
+        assert_eq!(second.count_ones(), second_naive.len());
+
+        let union = first.union(&second);
+        let intersection = first.intersection(&second);
+        assert_eq!(union.count_ones(), first_naive.union(&second_naive).count());
+        assert_eq!(intersection.count_ones(), first_naive.intersection(&second_naive).count());
+        assert!(intersection.count_ones() > 0);
+        assert_eq!(union.capacity(), 300);
+        for i in 0..300 {
+            assert_eq!(union.contains(i), first_naive.contains(&i) || second_naive.contains(&i));
+            assert_eq!(intersection.contains(i), first_naive.contains(&i) && second_naive.contains(&i));
+        }
+
+        let element = *first_naive.iter().next().unwrap();
+        first.clear(element);
+        assert!(!first.contains(element));
+        assert_eq!(first.count_ones(), first_naive.len() - 1);
+        assert!(!first.contains(1000));
+    }
+
+    /// Проверка упорядоченности кучи: ключ родителя не больше ключа ребенка
+    fn assert_fibonacci_heap_invariant<T: Ord + std::fmt::Debug>(heap: &FibonacciHeap<T>) {
+        let live: Vec<usize> = (0..heap.nodes.len())
+            .filter(|&i| heap.nodes[i].key.is_some())
+            .collect();
+        assert_eq!(live.len(), heap.len());
+        for &node in &live {
+            if let Some(parent) = heap.nodes[node].parent {
+                assert!(heap.key(parent) <= heap.key(node));
+            }
+            assert!(heap.peek().unwrap() <= heap.key(node));
+        }
+    }
+
+    #[test]
+    fn test_fibonacci_heap() {
+        let mut rng = rand::thread_rng();
+        let mut heap = FibonacciHeap::new();
+        let mut handles = Vec::new();
+        let mut keys = HashMap::new();
+        // Уникальные ключи, чтобы однозначно знать, какие узлы извлечены
+        let mut unique = HashSet::new();
+        while unique.len() < 200 {
+            unique.insert(rng.gen_range(0..10_000));
+        }
+        for key in unique {
+            let handle = heap.insert(key);
+            handles.push(handle);
+            keys.insert(handle, key);
+        }
+
+        // Извлечение строит деревья, чтобы decrease_key вырезал узлы
+        let mut extracted = Vec::new();
+        for _ in 0..20 {
+            extracted.push(heap.extract_min().unwrap());
+        }
+        let min_extracted = *extracted.last().unwrap();
+        assert_fibonacci_heap_invariant(&heap);
+
+        for handle in handles.iter().copied() {
+            let key = keys[&handle];
+            if key > min_extracted && rng.gen_bool(0.3) {
+                let new_key = rng.gen_range(min_extracted + 1..=key);
+                heap.decrease_key(handle, new_key);
+                keys.insert(handle, new_key);
+                assert_fibonacci_heap_invariant(&heap);
+            }
+        }
+
+        let mut expected: Vec<i32> = handles
+            .iter()
+            .map(|handle| keys[handle])
+            .filter(|key| !extracted.contains(key))
+            .collect();
+        expected.sort_unstable();
+        let mut remaining = Vec::new();
+        while let Some(key) = heap.extract_min() {
+            remaining.push(key);
+            if !heap.is_empty() {
+                assert_fibonacci_heap_invariant(&heap);
+            }
+        }
+        assert_eq!(remaining, expected);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_fibonacci_heap_merge() {
+        let mut first = FibonacciHeap::new();
+        let mut second = FibonacciHeap::new();
+        for key in [5, 8, 2] {
+            first.insert(key);
+        }
+        for key in [7, 1, 9] {
+            second.insert(key);
+        }
+        let handle = second.insert(10);
+
+        let remap = first.merge(second);
+        assert_eq!(first.len(), 7);
+        assert_eq!(first.peek(), Some(&1));
+        first.decrease_key(remap(handle), 0);
+        assert_fibonacci_heap_invariant(&first);
+
+        // Уменьшился именно ключ 10, а не узел с тем же индексом в `first`
+        let sorted: Vec<i32> = std::iter::from_fn(|| first.extract_min()).collect();
+        assert_eq!(sorted, vec![0, 1, 2, 5, 7, 8, 9]);
+    }
 } 
\ No newline at end of file
@@ -10,9 +10,26 @@
 //! - Энергосбережение
 //! - Отладка
 //! - Безопасность
+//! - Обновление прошивки с проверкой контрольной суммы
+//! - Конечный автомат состояний устройства
+//! - Кольцевой буфер приема UART
+//! - CRC-32 с таблицей
+//! - Битовые поля регистров на const generics
+//! - Сторожевой таймер (watchdog)
+//! - Ведущее устройство SPI
 
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::time::Duration;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Размер страницы флеш-памяти при записи прошивки
+const FLASH_PAGE_SIZE: usize = 256;
+
+/// Размер CRC32 в конце образа прошивки
+const CHECKSUM_SIZE: usize = 4;
 
 /// Структура для демонстрации работы с регистрами
 #[derive(Debug)]
@@ -44,6 +61,124 @@ pub struct PeripheralDemo {
     spi_config: u32,
 }
 
+/// Монотонный атомарный счетчик
+///
+/// `increment` публикует (Release) все записи, сделанные до него, а `get`
+/// их получает (Acquire), поэтому счетчик подходит как индекс записи.
+#[derive(Debug, Default)]
+pub struct AtomicCounter {
+    value: AtomicUsize,
+}
+
+/// Буфер UART заполнен, байт потерян
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Буфер приема UART заполнен")]
+pub struct BufferFull;
+
+/// Кольцевой буфер приема UART на `N` байт
+///
+/// Lock-free для одного писателя (обработчик прерывания) и одного читателя.
+/// Индексы растут монотонно, позиция в массиве — индекс по модулю `N`.
+/// Байты хранятся в `AtomicU8`, поэтому даже ошибочное использование
+/// несколькими писателями не приводит к гонке данных, только к потере байтов.
+#[derive(Debug)]
+pub struct UartRxBuffer<const N: usize> {
+    data: [AtomicU8; N],
+    write_index: AtomicCounter,
+    read_index: AtomicUsize,
+}
+
+/// Поле регистра шириной `WIDTH` бит, начиная с бита `OFFSET`
+///
+/// Тип не хранит данных: поля описываются псевдонимами вроде
+/// `type StatusField = BitField<4, 3>`, а методы работают со значением
+/// регистра. Поле, выходящее за 32 бита, не компилируется.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BitField<const OFFSET: u8, const WIDTH: u8>;
+
+/// Ведущее устройство SPI с программным выбором ведомого (chip select)
+///
+/// Для симуляции линия MISO замкнута на MOSI: при обмене ведущий получает
+/// обратно отправленные байты.
+#[derive(Debug)]
+pub struct SpiMaster {
+    clock_hz: u32,
+    chip_selected: bool,
+    sent: Vec<u8>,
+}
+
+/// Обработчик срабатывания сторожевого таймера
+type ExpireCallback = Box<dyn Fn() + Send>;
+
+/// Сторожевой таймер
+///
+/// Фоновый поток раз в `timeout` проверяет флаг `kicked`: если за период
+/// таймер не сбросили, вызывается `on_expire` и поток завершается.
+pub struct Watchdog {
+    timeout: Duration,
+    on_expire: Arc<Mutex<ExpireCallback>>,
+    kicked: Arc<AtomicBool>,
+    stopped: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+/// Отраженный полином CRC-32 (IEEE 802.3)
+const CRC32_POLYNOMIAL: u32 = 0xEDB8_8320;
+
+/// Вычисление CRC-32 (IEEE) по таблице из 256 значений
+///
+/// Таблица хранит остаток для каждого байта, поэтому байт обрабатывается
+/// одним поиском вместо восьми сдвигов.
+#[derive(Debug, Clone)]
+pub struct Crc32 {
+    table: [u32; 256],
+    state: u32,
+}
+
+/// Состояние устройства
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceState {
+    Uninitialized,
+    Initializing,
+    Ready,
+    Updating,
+    Error(String),
+}
+
+/// Действие недопустимо в текущем состоянии устройства
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Действие «{action}» недопустимо в состоянии {from}")]
+pub struct InvalidTransition {
+    pub from: DeviceState,
+    pub action: &'static str,
+}
+
+/// Ошибка обновления прошивки
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FirmwareError {
+    #[error(transparent)]
+    InvalidTransition(#[from] InvalidTransition),
+
+    #[error("Образ прошивки слишком мал: {0} байт")]
+    ImageTooSmall(usize),
+
+    #[error("Контрольная сумма не совпадает: ожидалась {expected:#010x}, вычислена {actual:#010x}")]
+    ChecksumMismatch { expected: u32, actual: u32 },
+}
+
+/// Устройство с обновляемой прошивкой
+///
+/// Допустимые переходы:
+/// `Uninitialized -> Initializing -> Ready -> Updating -> Ready`;
+/// из любого состояния возможен переход в `Error`, а `reset` возвращает
+/// устройство в `Uninitialized`.
+#[derive(Debug)]
+pub struct Device {
+    id: u32,
+    state: DeviceState,
+    firmware: Vec<u8>,
+}
+
 impl RegisterDemo {
     /// Создание нового экземпляра
     pub fn new() -> Self {
@@ -171,6 +306,389 @@ pub fn configure_spi(&mut self, mode: u8, clock_divider: u8) {
     }
 }
 
+impl AtomicCounter {
+    /// Создание счетчика с начальным значением
+    pub fn new(initial: usize) -> Self {
+        Self {
+            value: AtomicUsize::new(initial),
+        }
+    }
+
+    /// Увеличение на единицу; возвращает предыдущее значение
+    pub fn increment(&self) -> usize {
+        self.value.fetch_add(1, Ordering::AcqRel)
+    }
+
+    /// Текущее значение
+    pub fn get(&self) -> usize {
+        self.value.load(Ordering::Acquire)
+    }
+}
+
+impl<const N: usize> UartRxBuffer<N> {
+    /// Создание пустого буфера
+    pub fn new() -> Self {
+        assert!(N > 0, "буфер UART не может быть нулевого размера");
+        Self {
+            data: std::array::from_fn(|_| AtomicU8::new(0)),
+            write_index: AtomicCounter::new(0),
+            read_index: AtomicUsize::new(0),
+        }
+    }
+
+    /// Запись принятого байта; вызывается писателем
+    pub fn write_byte(&self, byte: u8) -> Result<(), BufferFull> {
+        let write = self.write_index.get();
+        let read = self.read_index.load(Ordering::Acquire);
+        if write.wrapping_sub(read) == N {
+            return Err(BufferFull);
+        }
+        self.data[write % N].store(byte, Ordering::Relaxed);
+        // Release в increment публикует записанный байт читателю
+        self.write_index.increment();
+        Ok(())
+    }
+
+    /// Чтение самого старого байта; вызывается читателем
+    pub fn read_byte(&self) -> Option<u8> {
+        let read = self.read_index.load(Ordering::Relaxed);
+        if read == self.write_index.get() {
+            return None;
+        }
+        let byte = self.data[read % N].load(Ordering::Relaxed);
+        // Release: писатель не перезапишет ячейку, пока не увидит новый индекс
+        self.read_index.store(read.wrapping_add(1), Ordering::Release);
+        Some(byte)
+    }
+
+    /// Количество непрочитанных байтов
+    pub fn len(&self) -> usize {
+        self.write_index.get().wrapping_sub(self.read_index.load(Ordering::Acquire))
+    }
+
+    /// Пуст ли буфер
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<const N: usize> Default for UartRxBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const OFFSET: u8, const WIDTH: u8> BitField<OFFSET, WIDTH> {
+    /// Проверка параметров при компиляции; вычисляется при мономорфизации,
+    /// поэтому глобальный `const _` с обобщенными параметрами не подходит
+    const VALID: () = assert!(
+        WIDTH > 0 && OFFSET as u32 + WIDTH as u32 <= 32,
+        "битовое поле должно помещаться в 32-битный регистр"
+    );
+
+    /// Маска поля, выровненная по младшему биту
+    pub const MASK: u32 = {
+        let () = Self::VALID;
+        u32::MAX >> (32 - WIDTH as u32)
+    };
+
+    /// Чтение значения поля
+    pub fn get(register: u32) -> u32 {
+        (register >> OFFSET) & Self::MASK
+    }
+
+    /// Запись значения в поле; лишние старшие биты `value` отбрасываются
+    pub fn set(register: u32, value: u32) -> u32 {
+        Self::clear(register) | ((value & Self::MASK) << OFFSET)
+    }
+
+    /// Обнуление поля
+    pub fn clear(register: u32) -> u32 {
+        register & !(Self::MASK << OFFSET)
+    }
+}
+
+impl SpiMaster {
+    /// Создание ведущего с частотой тактирования `clock_hz`
+    pub fn new(clock_hz: u32) -> Self {
+        assert!(clock_hz > 0, "частота SPI должна быть положительной");
+        Self {
+            clock_hz,
+            chip_selected: false,
+            sent: Vec::new(),
+        }
+    }
+
+    /// Активация линии CS ведомого
+    pub fn select(&mut self) {
+        self.chip_selected = true;
+    }
+
+    /// Снятие линии CS, завершение транзакции
+    pub fn deselect(&mut self) {
+        self.chip_selected = false;
+    }
+
+    /// Выбран ли ведомый
+    pub fn is_selected(&self) -> bool {
+        self.chip_selected
+    }
+
+    /// Полнодуплексный обмен: каждый байт `tx` отправляется, ответ пишется в `rx`
+    ///
+    /// Паникует, если ведомый не выбран или длины буферов различаются.
+    pub fn transfer(&mut self, tx: &[u8], rx: &mut [u8]) {
+        assert!(self.chip_selected, "обмен SPI без выбора ведомого");
+        assert_eq!(tx.len(), rx.len(), "буферы SPI разной длины");
+        self.sent.extend_from_slice(tx);
+        // Петля MISO -> MOSI
+        rx.copy_from_slice(tx);
+    }
+
+    /// Отправка данных без чтения ответа
+    pub fn write(&mut self, data: &[u8]) {
+        assert!(self.chip_selected, "обмен SPI без выбора ведомого");
+        self.sent.extend_from_slice(data);
+    }
+
+    /// Все отправленные байты
+    pub fn sent(&self) -> &[u8] {
+        &self.sent
+    }
+
+    /// Время передачи `bytes` байт на текущей частоте
+    pub fn transfer_duration(&self, bytes: usize) -> Duration {
+        Duration::from_secs_f64((bytes * 8) as f64 / f64::from(self.clock_hz))
+    }
+}
+
+impl Watchdog {
+    /// Создание остановленного таймера
+    pub fn new(timeout: Duration, on_expire: ExpireCallback) -> Self {
+        Self {
+            timeout,
+            on_expire: Arc::new(Mutex::new(on_expire)),
+            kicked: Arc::new(AtomicBool::new(false)),
+            stopped: Arc::new(AtomicBool::new(true)),
+            handle: None,
+        }
+    }
+
+    /// Запуск фонового потока; повторный вызов у запущенного таймера
+    /// ничего не делает, после срабатывания таймер запускается заново
+    pub fn start(&mut self) {
+        if self.handle.as_ref().is_some_and(|handle| !handle.is_finished()) {
+            return;
+        }
+        // Поток завершился после срабатывания: забираем его перед перезапуском
+        self.stop();
+        self.kicked.store(false, Ordering::SeqCst);
+        self.stopped.store(false, Ordering::SeqCst);
+
+        let timeout = self.timeout;
+        let on_expire = Arc::clone(&self.on_expire);
+        let kicked = Arc::clone(&self.kicked);
+        let stopped = Arc::clone(&self.stopped);
+        self.handle = Some(thread::spawn(move || {
+            let mut deadline = Instant::now() + timeout;
+            loop {
+                // park_timeout позволяет stop разбудить поток досрочно
+                thread::park_timeout(deadline.saturating_duration_since(Instant::now()));
+                if stopped.load(Ordering::SeqCst) {
+                    return;
+                }
+                if Instant::now() < deadline {
+                    continue;
+                }
+                if kicked.swap(false, Ordering::SeqCst) {
+                    deadline += timeout;
+                } else {
+                    (on_expire.lock().unwrap())();
+                    return;
+                }
+            }
+        }));
+    }
+
+    /// Сброс таймера: текущий период не приведет к срабатыванию
+    pub fn kick(&self) {
+        self.kicked.store(true, Ordering::SeqCst);
+    }
+
+    /// Остановка без вызова обработчика
+    pub fn stop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            self.stopped.store(true, Ordering::SeqCst);
+            handle.thread().unpark();
+            if handle.join().is_err() {
+                eprintln!("Обработчик сторожевого таймера завершился паникой");
+            }
+        }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl Crc32 {
+    /// Создание вычислителя с предрассчитанной таблицей
+    pub fn new() -> Self {
+        let mut table = [0u32; 256];
+        for (byte, entry) in table.iter_mut().enumerate() {
+            let mut crc = byte as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 { (crc >> 1) ^ CRC32_POLYNOMIAL } else { crc >> 1 };
+            }
+            *entry = crc;
+        }
+        Self { table, state: !0 }
+    }
+
+    /// Добавление данных к вычисляемой сумме
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            let index = (self.state ^ u32::from(byte)) & 0xFF;
+            self.state = (self.state >> 8) ^ self.table[index as usize];
+        }
+    }
+
+    /// Сумма всех переданных данных
+    pub fn finalize(&self) -> u32 {
+        !self.state
+    }
+
+    /// Сброс для вычисления новой суммы
+    pub fn reset(&mut self) {
+        self.state = !0;
+    }
+
+    /// Сумма одного блока данных
+    pub fn compute(data: &[u8]) -> u32 {
+        let mut crc = Self::new();
+        crc.update(data);
+        crc.finalize()
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for DeviceState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceState::Uninitialized => write!(f, "не инициализировано"),
+            DeviceState::Initializing => write!(f, "инициализация"),
+            DeviceState::Ready => write!(f, "готово"),
+            DeviceState::Updating => write!(f, "обновление"),
+            DeviceState::Error(reason) => write!(f, "ошибка ({})", reason),
+        }
+    }
+}
+
+impl Device {
+    /// Создание неинициализированного устройства
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            state: DeviceState::Uninitialized,
+            firmware: Vec::new(),
+        }
+    }
+
+    /// Текущее состояние
+    pub fn state(&self) -> &DeviceState {
+        &self.state
+    }
+
+    /// Инициализация; допустима только из `Uninitialized`
+    pub fn initialize(&mut self) -> Result<(), InvalidTransition> {
+        self.require(DeviceState::Uninitialized, "инициализация")?;
+        self.state = DeviceState::Initializing;
+        // Здесь будет настройка периферии
+        self.state = DeviceState::Ready;
+        Ok(())
+    }
+
+    /// Переход в состояние ошибки из любого состояния
+    pub fn report_error(&mut self, reason: impl Into<String>) {
+        self.state = DeviceState::Error(reason.into());
+    }
+
+    /// Сброс в `Uninitialized`; прошивка сохраняется
+    pub fn reset(&mut self) {
+        self.state = DeviceState::Uninitialized;
+    }
+
+    /// Текстовое описание состояния устройства
+    pub fn read_status(&self) -> String {
+        format!(
+            "Устройство {}: {}, прошивка {} байт",
+            self.id,
+            self.state,
+            self.firmware.len()
+        )
+    }
+
+    fn require(&self, expected: DeviceState, action: &'static str) -> Result<(), InvalidTransition> {
+        if self.state == expected {
+            Ok(())
+        } else {
+            Err(InvalidTransition {
+                from: self.state.clone(),
+                action,
+            })
+        }
+    }
+
+    /// Текущая прошивка
+    pub fn firmware(&self) -> &[u8] {
+        &self.firmware
+    }
+
+    /// Обновление прошивки; допустимо только в состоянии `Ready`
+    ///
+    /// Образ записывается страницами по 256 байт, после каждой страницы
+    /// `progress` получает процент выполнения. Последние 4 байта образа —
+    /// CRC32 (IEEE, little-endian) остальных данных. Прошивка заменяется
+    /// только после успешной проверки суммы, поэтому при ошибке устройство
+    /// возвращается в `Ready` со старой прошивкой.
+    pub fn firmware_update(&mut self, image: &[u8], progress: impl Fn(u8)) -> Result<(), FirmwareError> {
+        self.require(DeviceState::Ready, "обновление прошивки")?;
+        if image.len() <= CHECKSUM_SIZE {
+            return Err(FirmwareError::ImageTooSmall(image.len()));
+        }
+
+        self.state = DeviceState::Updating;
+        let result = self.write_firmware(image, progress);
+        self.state = DeviceState::Ready;
+        result
+    }
+
+    fn write_firmware(&mut self, image: &[u8], progress: impl Fn(u8)) -> Result<(), FirmwareError> {
+        let (payload, checksum) = image.split_at(image.len() - CHECKSUM_SIZE);
+        let pages = payload.len().div_ceil(FLASH_PAGE_SIZE);
+        let mut staging = Vec::with_capacity(payload.len());
+        for (written, page) in payload.chunks(FLASH_PAGE_SIZE).enumerate() {
+            staging.extend_from_slice(page);
+            progress(((written + 1) * 100 / pages) as u8);
+        }
+
+        let expected = u32::from_le_bytes(checksum.try_into().expect("ровно 4 байта"));
+        let actual = Crc32::compute(&staging);
+        if actual != expected {
+            return Err(FirmwareError::ChecksumMismatch { expected, actual });
+        }
+        self.firmware = staging;
+        Ok(())
+    }
+}
+
 /// Демонстрация работы с микроконтроллерами
 pub fn demonstrate_embedded_concepts() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n=== Демонстрация работы с микроконтроллерами ===");
@@ -205,6 +723,62 @@ pub fn demonstrate_embedded_concepts() -> Result<(), Box<dyn std::error::Error>>
     peripheral_demo.configure_uart(9600, 8, 1); // Настройка UART
     peripheral_demo.configure_spi(0, 4); // Настройка SPI
 
+    // Демонстрация обновления прошивки
+    println!("\n5. Обновление прошивки:");
+    let mut device = Device::new(1);
+    device.initialize()?;
+    let mut image = vec![0xA5; 600];
+    image.extend_from_slice(&Crc32::compute(&image).to_le_bytes());
+    device.firmware_update(&image, |percent| println!("Записано: {}%", percent))?;
+    println!("{}", device.read_status());
+
+    // Демонстрация буфера приема UART
+    println!("\n6. Буфер приема UART:");
+    let uart: UartRxBuffer<8> = UartRxBuffer::new();
+    for &byte in b"AT\r\n" {
+        uart.write_byte(byte)?;
+    }
+    let received: Vec<u8> = std::iter::from_fn(|| uart.read_byte()).collect();
+    println!("Принято: {:?}", String::from_utf8_lossy(&received));
+
+    // Демонстрация битовых полей
+    println!("\n7. Битовые поля:");
+    type Mode = BitField<0, 2>;
+    type Prescaler = BitField<8, 4>;
+    let control = Prescaler::set(Mode::set(0, 0b10), 9);
+    println!(
+        "Регистр {:#010x}: режим={}, предделитель={}",
+        control,
+        Mode::get(control),
+        Prescaler::get(control)
+    );
+
+    // Демонстрация сторожевого таймера
+    println!("\n8. Сторожевой таймер:");
+    let mut watchdog = Watchdog::new(
+        Duration::from_millis(50),
+        Box::new(|| println!("Сторожевой таймер сработал: перезагрузка")),
+    );
+    watchdog.start();
+    thread::sleep(Duration::from_millis(30));
+    watchdog.kick();
+    thread::sleep(Duration::from_millis(120));
+    watchdog.stop();
+
+    // Демонстрация SPI
+    println!("\n9. SPI:");
+    let mut spi = SpiMaster::new(1_000_000);
+    let mut response = [0u8; 3];
+    spi.select();
+    spi.write(&[0x9F]);
+    spi.transfer(&[0xEF, 0x40, 0x18], &mut response);
+    spi.deselect();
+    println!(
+        "Ответ: {:02X?}, время обмена: {:?}",
+        response,
+        spi.transfer_duration(spi.sent().len())
+    );
+
     Ok(())
 }
 
@@ -244,4 +818,241 @@ fn test_peripheral_operations() {
         demo.configure_gpio(0, 1);
         assert_eq!(demo.gpio_config & 0b11, 1);
     }
+
+    /// Образ прошивки с CRC32 в последних 4 байтах
+    fn firmware_image(len: usize) -> Vec<u8> {
+        let mut image: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+        image.extend_from_slice(&Crc32::compute(&image).to_le_bytes());
+        image
+    }
+
+    #[test]
+    fn test_crc32_known_value() {
+        assert_eq!(Crc32::compute(b"123456789"), 0xCBF4_3926);
+        assert_eq!(Crc32::compute(b""), 0);
+    }
+
+    #[test]
+    fn test_crc32_incremental_matches_one_shot() {
+        let data: Vec<u8> = (0..1000).map(|i| (i * 31 % 251) as u8).collect();
+        let mut crc = Crc32::new();
+        for chunk in data.chunks(37) {
+            crc.update(chunk);
+        }
+        assert_eq!(crc.finalize(), Crc32::compute(&data));
+
+        crc.reset();
+        crc.update(b"1234");
+        crc.update(b"56789");
+        assert_eq!(crc.finalize(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_firmware_update() {
+        let mut device = Device::new(1);
+        device.initialize().unwrap();
+        let image = firmware_image(1000);
+
+        let reported = std::cell::RefCell::new(Vec::new());
+        device
+            .firmware_update(&image, |percent| reported.borrow_mut().push(percent))
+            .unwrap();
+        // 1000 байт — это 4 страницы по 256 байт
+        assert_eq!(*reported.borrow(), vec![25, 50, 75, 100]);
+        assert_eq!(device.firmware(), &image[..1000]);
+    }
+
+    #[test]
+    fn test_firmware_update_checksum_mismatch() {
+        let mut device = Device::new(1);
+        device.initialize().unwrap();
+        let mut image = firmware_image(300);
+        image[10] ^= 0xFF;
+
+        let reported = std::cell::RefCell::new(Vec::new());
+        let result = device.firmware_update(&image, |percent| reported.borrow_mut().push(percent));
+        assert!(matches!(result, Err(FirmwareError::ChecksumMismatch { .. })));
+        assert_eq!(*reported.borrow(), vec![50, 100]);
+        // Поврежденный образ не заменяет прошивку
+        assert!(device.firmware().is_empty());
+
+        assert_eq!(
+            device.firmware_update(&[1, 2, 3], |_| {}),
+            Err(FirmwareError::ImageTooSmall(3))
+        );
+    }
+
+    #[test]
+    fn test_device_state_machine() {
+        let mut device = Device::new(7);
+        assert_eq!(device.state(), &DeviceState::Uninitialized);
+
+        // Обновление до инициализации запрещено
+        assert_eq!(
+            device.firmware_update(&firmware_image(10), |_| {}),
+            Err(FirmwareError::InvalidTransition(InvalidTransition {
+                from: DeviceState::Uninitialized,
+                action: "обновление прошивки",
+            }))
+        );
+
+        device.initialize().unwrap();
+        assert_eq!(device.state(), &DeviceState::Ready);
+        assert!(device.initialize().is_err());
+
+        let pages = std::cell::Cell::new(0);
+        device.firmware_update(&firmware_image(10), |_| pages.set(pages.get() + 1)).unwrap();
+        assert_eq!(pages.get(), 1);
+        assert_eq!(device.state(), &DeviceState::Ready);
+
+        device.report_error("перегрев");
+        assert_eq!(device.state(), &DeviceState::Error("перегрев".to_string()));
+        assert!(device.firmware_update(&firmware_image(10), |_| {}).is_err());
+        assert!(device.initialize().is_err());
+
+        device.reset();
+        device.initialize().unwrap();
+        assert_eq!(device.firmware().len(), 10);
+    }
+
+    #[test]
+    fn test_uart_rx_buffer_full_and_order() {
+        let buffer: UartRxBuffer<16> = UartRxBuffer::new();
+        for byte in 0..16 {
+            buffer.write_byte(byte).unwrap();
+        }
+        assert_eq!(buffer.len(), 16);
+        assert_eq!(buffer.write_byte(99), Err(BufferFull));
+
+        let read: Vec<u8> = std::iter::from_fn(|| buffer.read_byte()).collect();
+        assert_eq!(read, (0..16).collect::<Vec<u8>>());
+        assert!(buffer.is_empty());
+
+        // После чтения буфер снова принимает данные с переходом через край
+        buffer.write_byte(100).unwrap();
+        assert_eq!(buffer.read_byte(), Some(100));
+        assert_eq!(buffer.read_byte(), None);
+    }
+
+    #[test]
+    fn test_uart_rx_buffer_spsc() {
+        let buffer = std::sync::Arc::new(UartRxBuffer::<32>::new());
+        let producer = {
+            let buffer = std::sync::Arc::clone(&buffer);
+            std::thread::spawn(move || {
+                for i in 0..10_000u32 {
+                    while buffer.write_byte(i as u8).is_err() {
+                        std::thread::yield_now();
+                    }
+                }
+            })
+        };
+
+        let mut received = 0u32;
+        while received < 10_000 {
+            match buffer.read_byte() {
+                Some(byte) => {
+                    assert_eq!(byte, received as u8);
+                    received += 1;
+                }
+                None => std::thread::yield_now(),
+            }
+        }
+        producer.join().unwrap();
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_bit_field_round_trip() {
+        type StatusField = BitField<4, 3>;
+        assert_eq!(StatusField::MASK, 0b111);
+
+        for register in [0u32, u32::MAX, 0xA5A5_A5A5] {
+            for value in 0..8 {
+                let updated = StatusField::set(register, value);
+                assert_eq!(StatusField::get(updated), value);
+                // Биты вне поля не меняются
+                assert_eq!(updated & !(0b111 << 4), register & !(0b111 << 4));
+            }
+            assert_eq!(StatusField::get(StatusField::clear(register)), 0);
+        }
+
+        // Лишние биты значения отбрасываются
+        assert_eq!(StatusField::set(0, 0b1010), 0b010 << 4);
+        type Whole = BitField<0, 32>;
+        assert_eq!(Whole::get(Whole::set(0, u32::MAX)), u32::MAX);
+    }
+
+    #[test]
+    fn test_watchdog_kick_and_expire() {
+        let fired = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&fired);
+        let mut watchdog = Watchdog::new(
+            Duration::from_millis(200),
+            Box::new(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+
+        watchdog.start();
+        thread::sleep(Duration::from_millis(100));
+        watchdog.kick();
+        thread::sleep(Duration::from_millis(100));
+        watchdog.kick();
+        thread::sleep(Duration::from_millis(100));
+        watchdog.stop();
+        assert_eq!(fired.load(Ordering::SeqCst), 0);
+
+        // Без сброса таймер срабатывает ровно один раз
+        watchdog.start();
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+        watchdog.stop();
+    }
+
+    #[test]
+    fn test_watchdog_restart_after_expire() {
+        let fired = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&fired);
+        let mut watchdog = Watchdog::new(
+            Duration::from_millis(50),
+            Box::new(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+
+        watchdog.start();
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+
+        // Сработавший таймер запускается повторно без явного stop
+        watchdog.start();
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(fired.load(Ordering::SeqCst), 2);
+        watchdog.stop();
+    }
+
+    #[test]
+    fn test_spi_transfer_loopback() {
+        let mut spi = SpiMaster::new(8_000_000);
+        let tx = [0xDE, 0xAD, 0xBE, 0xEF];
+        let mut rx = [0u8; 4];
+
+        spi.select();
+        assert!(spi.is_selected());
+        spi.transfer(&tx, &mut rx);
+        spi.deselect();
+
+        assert_eq!(rx, tx);
+        assert!(!spi.is_selected());
+        assert_eq!(spi.sent(), &tx);
+        assert_eq!(spi.transfer_duration(4), Duration::from_micros(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "без выбора ведомого")]
+    fn test_spi_transfer_requires_chip_select() {
+        let mut spi = SpiMaster::new(1_000_000);
+        spi.write(&[1, 2, 3]);
+    }
 } 
\ No newline at end of file
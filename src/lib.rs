@@ -28,6 +28,9 @@
 pub mod networking;
 pub mod database;
 pub mod embedded;
+pub mod optimization;
+pub mod metrics;
+pub mod security;
 
 // Реэкспорт основных типов
 pub use memory::{HeapData, StackData};
@@ -36,5 +39,5 @@
 pub use data_structures::{ComplexData, OptimizedData};
 pub use algorithms::{SortingAlgorithms, SearchingAlgorithms};
 pub use networking::{HttpServer, WebSocketClient, UdpServer};
-pub use database::{Database, User, UserRepository};
+pub use database::{Database, MigrationRunner, Page, User, UserRepository};
 pub use embedded::{BitField, AtomicCounter, TimeInterval, Device, DeviceState}; 
\ No newline at end of file
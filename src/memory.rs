@@ -6,12 +6,21 @@
 //! - Оптимизация использования памяти
 //! - Паттерны управления памятью
 //! - Работа с небезопасным кодом
+//! - Арена с bump-аллокацией
+//! - Пул объектов фиксированного размера
+//! - Менеджер памяти с учетом пикового потребления
+//! - Копирование при записи (copy-on-write)
 
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::cell::RefCell;
+use std::alloc::{self, Layout};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell, UnsafeCell};
 use std::mem;
-use std::ptr;
+use std::ops::{Deref, DerefMut};
+use std::ptr::{self, NonNull};
+use thiserror::Error;
 
 /// Структура для демонстрации размещения данных в стеке
 #[derive(Debug)]
@@ -45,6 +54,370 @@ pub struct UnsafeDemo {
     size: usize,
 }
 
+/// Выравнивание буфера арены, подходящее для всех примитивных типов
+const ARENA_ALIGN: usize = 16;
+
+/// Ошибка переполнения арены
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Арена переполнена: запрошено {requested} байт, свободно {available}")]
+pub struct OutOfMemory {
+    pub requested: usize,
+    pub available: usize,
+}
+
+/// Арена: значения размещаются в одном буфере сдвигом указателя
+///
+/// Отдельного освобождения нет, весь буфер освобождается вместе с ареной.
+/// Деструкторы размещенных значений не вызываются.
+pub struct Arena {
+    buffer: NonNull<u8>,
+    capacity: usize,
+    offset: Cell<usize>,
+}
+
+impl Arena {
+    /// Создание арены заданной емкости в байтах
+    pub fn new(capacity: usize) -> Self {
+        let buffer = NonNull::new(unsafe { alloc::alloc(Self::layout(capacity)) })
+            .unwrap_or_else(|| alloc::handle_alloc_error(Self::layout(capacity)));
+        Self {
+            buffer,
+            capacity,
+            offset: Cell::new(0),
+        }
+    }
+
+    /// Размещение значения в арене
+    ///
+    /// Ссылка живет не дольше арены, поэтому память не может быть
+    /// освобождена раньше времени.
+    #[allow(clippy::mut_from_ref)] // каждый вызов выдает новую непересекающуюся область
+    pub fn alloc<T>(&self, value: T) -> Result<&mut T, OutOfMemory> {
+        let layout = Layout::new::<T>();
+        let base = self.buffer.as_ptr() as usize;
+        let offset = self.offset.get();
+        let available = self.capacity - offset;
+
+        // Округляем адрес вверх до выравнивания T
+        let start = (base + offset)
+            .checked_add(layout.align() - 1)
+            .map(|addr| addr & !(layout.align() - 1))
+            .map(|addr| addr - base);
+        let end = start.and_then(|start| start.checked_add(layout.size()));
+        let (start, end) = match (start, end) {
+            (Some(start), Some(end)) if end <= self.capacity => (start, end),
+            _ => {
+                return Err(OutOfMemory {
+                    requested: layout.size(),
+                    available,
+                })
+            }
+        };
+
+        self.offset.set(end);
+        unsafe {
+            let slot = self.buffer.as_ptr().add(start) as *mut T;
+            slot.write(value);
+            Ok(&mut *slot)
+        }
+    }
+
+    /// Занятые байты, включая отступы выравнивания
+    pub fn used(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// Емкость арены в байтах
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn layout(capacity: usize) -> Layout {
+        // Нулевой размер запрещен для alloc, поэтому минимум один байт
+        Layout::from_size_align(capacity.max(1), ARENA_ALIGN).expect("слишком большая арена")
+    }
+}
+
+impl Drop for Arena {
+    fn drop(&mut self) {
+        unsafe { alloc::dealloc(self.buffer.as_ptr(), Self::layout(self.capacity)) }
+    }
+}
+
+/// Пул объектов: слоты выделяются заранее и переиспользуются через список свободных
+///
+/// Выделение и освобождение не обращаются к аллокатору. Значение
+/// освобожденного слота не сбрасывается и достается следующему владельцу.
+pub struct MemoryPool<T> {
+    slots: Box<[UnsafeCell<T>]>,
+    free: RefCell<Vec<usize>>,
+}
+
+/// Занятый слот пула, при удалении возвращается в пул
+pub struct PoolSlot<'a, T> {
+    pool: &'a MemoryPool<T>,
+    index: usize,
+}
+
+impl<T: Default> MemoryPool<T> {
+    /// Создание пула на `capacity` объектов
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            slots: (0..capacity).map(|_| UnsafeCell::new(T::default())).collect(),
+            // Выдаем слоты с начала, поэтому список развернут
+            free: RefCell::new((0..capacity).rev().collect()),
+        }
+    }
+}
+
+impl<T> MemoryPool<T> {
+    /// Выделение слота, `None` если пул исчерпан
+    pub fn alloc(&self) -> Option<PoolSlot<'_, T>> {
+        let index = self.free.borrow_mut().pop()?;
+        Some(PoolSlot { pool: self, index })
+    }
+
+    /// Явное освобождение слота
+    pub fn free(slot: PoolSlot<'_, T>) {
+        drop(slot);
+    }
+
+    /// Количество свободных слотов
+    pub fn available(&self) -> usize {
+        self.free.borrow().len()
+    }
+
+    /// Емкость пула
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+impl<T> Deref for PoolSlot<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Индекс выдан только этому слоту, пока он не вернется в список свободных
+        unsafe { &*self.pool.slots[self.index].get() }
+    }
+}
+
+impl<T> DerefMut for PoolSlot<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.pool.slots[self.index].get() }
+    }
+}
+
+impl<T> Drop for PoolSlot<'_, T> {
+    fn drop(&mut self) {
+        self.pool.free.borrow_mut().push(self.index);
+    }
+}
+
+/// Идентификатор блока в `MemoryManager`: номер слота и поколение
+///
+/// Поколения выдаются из общего счетчика менеджера, поэтому старый
+/// идентификатор не указывает на новый блок в том же слоте даже после
+/// `shrink_to_fit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockId {
+    slot: usize,
+    generation: u64,
+}
+
+/// Положение блока в куче менеджера
+#[derive(Debug, Clone, Copy)]
+struct Block {
+    offset: usize,
+    size: usize,
+    generation: u64,
+}
+
+/// Менеджер памяти: выделяет блоки из собственной кучи методом first-fit
+pub struct MemoryManager {
+    heap: Vec<u8>,
+    blocks: Vec<Option<Block>>,
+    next_generation: u64,
+    current_bytes: AtomicUsize,
+    peak_bytes: Arc<AtomicUsize>,
+}
+
+impl MemoryManager {
+    /// Создание менеджера с кучей заданного размера
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            heap: vec![0; capacity],
+            blocks: Vec::new(),
+            next_generation: 0,
+            current_bytes: AtomicUsize::new(0),
+            peak_bytes: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Выделение блока в первом подходящем промежутке
+    pub fn allocate(&mut self, size: usize) -> Result<BlockId, OutOfMemory> {
+        let offset = self.find_gap(size).ok_or(OutOfMemory {
+            requested: size,
+            available: self.heap.len() - self.current_usage(),
+        })?;
+
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        let block = Block { offset, size, generation };
+        let slot = match self.blocks.iter().position(Option::is_none) {
+            Some(index) => {
+                self.blocks[index] = Some(block);
+                index
+            }
+            None => {
+                self.blocks.push(Some(block));
+                self.blocks.len() - 1
+            }
+        };
+
+        let current = self.current_bytes.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak_bytes.fetch_max(current, Ordering::Relaxed);
+        Ok(BlockId { slot, generation })
+    }
+
+    /// Освобождение блока, `false` если идентификатор устарел
+    pub fn deallocate(&mut self, id: BlockId) -> bool {
+        let Some(block) = self.live_block(id) else {
+            return false;
+        };
+        self.blocks[id.slot] = None;
+        self.current_bytes.fetch_sub(block.size, Ordering::Relaxed);
+        true
+    }
+
+    /// Содержимое блока
+    pub fn block(&self, id: BlockId) -> Option<&[u8]> {
+        let block = self.live_block(id)?;
+        Some(&self.heap[block.offset..block.offset + block.size])
+    }
+
+    /// Изменяемое содержимое блока
+    pub fn block_mut(&mut self, id: BlockId) -> Option<&mut [u8]> {
+        let block = self.live_block(id)?;
+        Some(&mut self.heap[block.offset..block.offset + block.size])
+    }
+
+    /// Блок по идентификатору, если слот занят тем же поколением
+    fn live_block(&self, id: BlockId) -> Option<Block> {
+        self.blocks
+            .get(id.slot)
+            .copied()
+            .flatten()
+            .filter(|block| block.generation == id.generation)
+    }
+
+    /// Текущий объем выделенной памяти в байтах
+    pub fn current_usage(&self) -> usize {
+        self.current_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Максимальный объем выделенной памяти за все время
+    pub fn peak_usage(&self) -> usize {
+        self.peak_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Счетчик пика для внешнего мониторинга
+    pub fn peak_counter(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.peak_bytes)
+    }
+
+    /// Освобождение лишней памяти списка блоков
+    ///
+    /// Размер самой кучи фиксирован и не уменьшается.
+    pub fn shrink_to_fit(&mut self) {
+        while matches!(self.blocks.last(), Some(None)) {
+            self.blocks.pop();
+        }
+        self.blocks.shrink_to_fit();
+    }
+
+    /// Сдвиг живых блоков к началу кучи с копированием данных
+    ///
+    /// Возвращает число байт, которые были разбросаны по промежуткам
+    /// между блоками и теперь объединены в один свободный участок.
+    pub fn defragment(&mut self) -> usize {
+        let mut live: Vec<(usize, Block)> = self
+            .blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(index, block)| block.map(|block| (index, block)))
+            .collect();
+        live.sort_by_key(|(_, block)| block.offset);
+
+        let old_end = live.last().map_or(0, |(_, block)| block.offset + block.size);
+        let mut cursor = 0;
+        for (index, block) in live {
+            if block.offset != cursor {
+                self.heap.copy_within(block.offset..block.offset + block.size, cursor);
+                self.blocks[index] = Some(Block { offset: cursor, ..block });
+            }
+            cursor += block.size;
+        }
+        old_end - cursor
+    }
+
+    /// Поиск первого промежутка между блоками, куда помещается `size` байт
+    fn find_gap(&self, size: usize) -> Option<usize> {
+        let mut live: Vec<Block> = self.blocks.iter().flatten().copied().collect();
+        live.sort_by_key(|block| block.offset);
+
+        let mut cursor = 0;
+        for block in live {
+            if block.offset - cursor >= size {
+                return Some(cursor);
+            }
+            cursor = block.offset + block.size;
+        }
+        (self.heap.len() - cursor >= size).then_some(cursor)
+    }
+}
+
+/// Буфер с копированием при записи: заимствует срез до первого изменения
+#[derive(Debug, Clone)]
+pub struct CowBuffer<'a> {
+    data: Cow<'a, [u8]>,
+}
+
+impl<'a> CowBuffer<'a> {
+    /// Буфер поверх заимствованного среза, без выделения памяти
+    pub fn borrowed(data: &'a [u8]) -> Self {
+        Self { data: Cow::Borrowed(data) }
+    }
+
+    /// Буфер, сразу владеющий данными
+    pub fn owned(data: Vec<u8>) -> Self {
+        Self { data: Cow::Owned(data) }
+    }
+
+    /// Владеет ли буфер собственной копией данных
+    pub fn is_owned(&self) -> bool {
+        matches!(self.data, Cow::Owned(_))
+    }
+
+    /// Доступ на запись; при первом вызове данные копируются
+    pub fn make_mut(&mut self) -> &mut Vec<u8> {
+        self.data.to_mut()
+    }
+
+    /// Извлечение данных с копированием, если они еще заимствованы
+    pub fn into_owned(self) -> Vec<u8> {
+        self.data.into_owned()
+    }
+}
+
+impl Deref for CowBuffer<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
 impl StackData {
     /// Создание данных в стеке
     pub fn new() -> Self {
@@ -177,6 +550,42 @@ pub fn demonstrate_memory_differences() -> Result<(), Box<dyn std::error::Error>
         unsafe_demo.demonstrate_unsafe();
     }
 
+    // Демонстрация арены
+    println!("\n5. Арена:");
+    let arena = Arena::new(1024);
+    let number = arena.alloc(42u64)?;
+    let text = arena.alloc(String::from("в арене"))?;
+    *number += 1;
+    println!("Значения: {} и {:?}, занято {} из {} байт", number, text, arena.used(), arena.capacity());
+
+    // Демонстрация пула объектов
+    println!("\n6. Пул объектов:");
+    let pool: MemoryPool<[u8; 32]> = MemoryPool::new(4);
+    let mut buffer = pool.alloc().ok_or("пул исчерпан")?;
+    buffer[0] = 1;
+    println!("Свободно слотов: {} из {}", pool.available(), pool.capacity());
+    MemoryPool::free(buffer);
+    println!("После освобождения: {}", pool.available());
+
+    // Демонстрация менеджера памяти
+    println!("\n7. Менеджер памяти:");
+    let mut manager = MemoryManager::new(4096);
+    let first = manager.allocate(1024)?;
+    let second = manager.allocate(2048)?;
+    manager.deallocate(first);
+    manager.block_mut(second).ok_or("блок освобожден")?.fill(1);
+    println!("Текущее потребление: {} байт, пик: {} байт", manager.current_usage(), manager.peak_usage());
+    println!("Дефрагментация освободила {} байт", manager.defragment());
+    manager.shrink_to_fit();
+
+    // Демонстрация копирования при записи
+    println!("\n8. Копирование при записи:");
+    let source = [1u8, 2, 3];
+    let mut buffer = CowBuffer::borrowed(&source);
+    println!("До изменения: {:?}, владеет: {}", &*buffer, buffer.is_owned());
+    buffer.make_mut().push(4);
+    println!("После изменения: {:?}, владеет: {}", &*buffer, buffer.is_owned());
+
     Ok(())
 }
 
@@ -216,4 +625,149 @@ fn test_unsafe_demo() {
             assert!(!demo.raw_ptr.is_null());
         }
     }
+
+    #[test]
+    fn test_arena_alloc() {
+        let arena = Arena::new(1000 * mem::size_of::<u64>());
+        let values: Vec<&mut u64> = (0..1000u64).map(|i| arena.alloc(i * 3).unwrap()).collect();
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(**value, i as u64 * 3);
+        }
+        assert_eq!(arena.used(), arena.capacity());
+
+        let err = arena.alloc(1u8).unwrap_err();
+        assert_eq!(err, OutOfMemory { requested: 1, available: 0 });
+    }
+
+    #[test]
+    fn test_arena_alignment() {
+        let arena = Arena::new(64);
+        let byte = arena.alloc(1u8).unwrap();
+        let wide = arena.alloc(2u64).unwrap();
+        assert_eq!(wide as *mut u64 as usize % mem::align_of::<u64>(), 0);
+        // Отступ выравнивания тоже занят
+        assert_eq!(arena.used(), 16);
+        *byte = 7;
+        assert_eq!((*byte, *wide), (7, 2));
+
+        assert!(arena.alloc([0u8; 64]).is_err());
+    }
+
+    #[test]
+    fn test_memory_pool() {
+        let pool: MemoryPool<u32> = MemoryPool::new(3);
+        let mut slots: Vec<PoolSlot<u32>> = (0..3).map(|_| pool.alloc().unwrap()).collect();
+        for (i, slot) in slots.iter_mut().enumerate() {
+            **slot = i as u32 * 10;
+        }
+        assert!(pool.alloc().is_none());
+        assert_eq!(slots.iter().map(|slot| **slot).collect::<Vec<_>>(), vec![0, 10, 20]);
+
+        MemoryPool::free(slots.remove(1));
+        assert_eq!(pool.available(), 1);
+        let reused = pool.alloc().unwrap();
+        // Слот переиспользован вместе со старым значением
+        assert_eq!(*reused, 10);
+        assert!(pool.alloc().is_none());
+    }
+
+    #[test]
+    fn test_memory_manager_peak_usage() {
+        const MB: usize = 1024 * 1024;
+        let mut manager = MemoryManager::new(16 * MB);
+
+        let blocks: Vec<BlockId> = (0..10).map(|_| manager.allocate(MB).unwrap()).collect();
+        for &id in &blocks[..5] {
+            assert!(manager.deallocate(id));
+        }
+        assert!(!manager.deallocate(blocks[0]));
+
+        let big = manager.allocate(3 * MB).unwrap();
+        manager.block_mut(big).unwrap().fill(7);
+        assert!(manager.block(blocks[5]).unwrap().iter().all(|&byte| byte == 0));
+
+        assert_eq!(manager.peak_usage(), 10 * MB);
+        assert_eq!(manager.current_usage(), 8 * MB);
+        assert_eq!(manager.peak_counter().load(Ordering::Relaxed), 10 * MB);
+    }
+
+    #[test]
+    fn test_cow_buffer() {
+        let source = vec![1u8, 2, 3];
+        let mut first = CowBuffer::borrowed(&source);
+        let second = CowBuffer::borrowed(&source);
+
+        // Оба буфера читают исходный срез без копирования
+        assert!(!first.is_owned() && !second.is_owned());
+        assert_eq!(first.as_ptr(), source.as_ptr());
+        assert_eq!(second.as_ptr(), source.as_ptr());
+
+        first.make_mut()[0] = 10;
+        assert!(first.is_owned());
+        assert_ne!(first.as_ptr(), source.as_ptr());
+        assert_eq!(&*first, &[10, 2, 3]);
+
+        assert!(!second.is_owned());
+        assert_eq!(&*second, &[1, 2, 3]);
+        assert_eq!(second.into_owned(), source);
+    }
+
+    #[test]
+    fn test_memory_manager_defragment() {
+        let mut manager = MemoryManager::new(1000);
+        let blocks: Vec<BlockId> = (0..10).map(|_| manager.allocate(100).unwrap()).collect();
+        for (i, &id) in blocks.iter().enumerate() {
+            manager.block_mut(id).unwrap().fill(i as u8);
+        }
+        for &id in blocks.iter().step_by(2) {
+            manager.deallocate(id);
+        }
+
+        // Свободно 500 байт, но кусками по 100
+        assert!(manager.allocate(500).is_err());
+
+        assert_eq!(manager.defragment(), 500);
+        for (i, &id) in blocks.iter().enumerate().skip(1).step_by(2) {
+            assert!(manager.block(id).unwrap().iter().all(|&byte| byte == i as u8));
+        }
+
+        let combined = manager.allocate(500).unwrap();
+        assert_eq!(manager.block(combined).unwrap().len(), 500);
+        assert_eq!(manager.defragment(), 0);
+    }
+
+    #[test]
+    fn test_memory_manager_shrink_to_fit() {
+        let mut manager = MemoryManager::new(100);
+        let kept = manager.allocate(10).unwrap();
+        let freed = manager.allocate(10).unwrap();
+        manager.deallocate(freed);
+
+        manager.shrink_to_fit();
+        assert_eq!(manager.block(kept).unwrap().len(), 10);
+        assert!(manager.block(freed).is_none());
+        assert!(!manager.deallocate(freed));
+    }
+
+    #[test]
+    fn test_memory_manager_stale_id_after_reuse() {
+        let mut manager = MemoryManager::new(100);
+        let first = manager.allocate(10).unwrap();
+        assert!(manager.deallocate(first));
+
+        // Новый блок занимает тот же слот, старый идентификатор его не видит
+        let reused = manager.allocate(20).unwrap();
+        assert!(!manager.deallocate(first));
+        assert!(manager.block(first).is_none());
+        assert_eq!(manager.block(reused).unwrap().len(), 20);
+        assert_eq!(manager.current_usage(), 20);
+
+        // После shrink_to_fit слот создается заново, но поколение не повторяется
+        assert!(manager.deallocate(reused));
+        manager.shrink_to_fit();
+        let fresh = manager.allocate(30).unwrap();
+        assert!(!manager.deallocate(first));
+        assert!(!manager.deallocate(reused));
+        assert_eq!(manager.block(fresh).unwrap().len(), 30);
+    }
 } 
\ No newline at end of file
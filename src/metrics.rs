@@ -11,12 +11,17 @@
 //! - Отслеживание ресурсов
 //! - Мониторинг состояния
 //! - Метрики бизнес-логики
+//! - История снимков метрик и тренды
+//! - Экспорт в формате Prometheus
+//! - Счетчик в скользящем окне
 
+use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
 use std::sync::Arc;
 use parking_lot::Mutex;
 use metrics::{counter, gauge, histogram};
 use metrics_exporter_prometheus::PrometheusBuilder;
+use prometheus::{Encoder, Gauge, Histogram, HistogramOpts, IntCounter, Registry, TextEncoder};
 use tracing::{info, warn, error, Level};
 use tracing_subscriber::FmtSubscriber;
 
@@ -28,11 +33,41 @@ pub struct MetricsDemo {
     active_connections: Arc<Mutex<u32>>,
 }
 
+/// Число хранимых снимков метрик по умолчанию
+const DEFAULT_HISTORY_LIMIT: usize = 60;
+
 /// Структура для демонстрации мониторинга
 #[derive(Debug)]
 pub struct MonitoringDemo {
     metrics: Arc<Mutex<HashMap<String, f64>>>,
     alerts: Vec<Alert>,
+    /// Скользящее окно снимков метрик, от старых к новым
+    history: VecDeque<(Instant, HashMap<String, f64>)>,
+    history_limit: usize,
+}
+
+/// Экспорт метрик напрямую через крейт `prometheus`, без фасада `metrics`
+///
+/// Метрики регистрируются в собственном реестре, а `render` выдает их в
+/// текстовом формате Prometheus для эндпоинта `/metrics`.
+#[derive(Debug, Clone, Default)]
+pub struct PrometheusExporter {
+    registry: Registry,
+}
+
+/// Счетчик событий за последние `window` с точностью до корзины
+///
+/// Окно делится на `bucket_count` корзин одинаковой ширины; границы корзин
+/// отсчитываются от момента создания счетчика. Корзина учитывается, пока
+/// ее конец попадает в окно. Подходит для ограничения частоты запросов и
+/// поиска всплесков.
+#[derive(Debug)]
+pub struct SlidingWindowCounter {
+    window: Duration,
+    bucket_width: Duration,
+    origin: Instant,
+    /// Начало корзины и сумма значений в ней, от старых к новым
+    buckets: VecDeque<(Instant, u64)>,
 }
 
 #[derive(Debug)]
@@ -82,12 +117,57 @@ pub fn measure_execution_time<F, T>(&self, name: &str, f: F) -> T
 impl MonitoringDemo {
     /// Создание нового экземпляра
     pub fn new() -> Self {
+        Self::with_history_limit(DEFAULT_HISTORY_LIMIT)
+    }
+
+    /// Создание экземпляра, хранящего не больше `limit` снимков
+    pub fn with_history_limit(limit: usize) -> Self {
         Self {
             metrics: Arc::new(Mutex::new(HashMap::new())),
             alerts: Vec::new(),
+            history: VecDeque::with_capacity(limit),
+            history_limit: limit,
+        }
+    }
+
+    /// Сохранение текущих значений метрик в историю
+    pub fn snapshot(&mut self) {
+        let metrics = self.metrics.lock().clone();
+        self.history.push_back((Instant::now(), metrics));
+        while self.history.len() > self.history_limit {
+            self.history.pop_front();
         }
     }
 
+    /// Скорость изменения метрики в секунду между первым и последним
+    /// снимком окна, где она присутствует
+    pub fn trend(&self, metric_name: &str) -> Option<f64> {
+        let mut points = self
+            .history
+            .iter()
+            .filter_map(|(time, metrics)| metrics.get(metric_name).map(|&value| (*time, value)));
+        let (first_time, first_value) = points.next()?;
+        let (last_time, last_value) = points.last()?;
+        let elapsed = last_time.duration_since(first_time).as_secs_f64();
+        (elapsed > 0.0).then(|| (last_value - first_value) / elapsed)
+    }
+
+    /// Удаление снимков старше `age`
+    pub fn prune_older_than(&mut self, age: Duration) {
+        let now = Instant::now();
+        while let Some((time, _)) = self.history.front() {
+            if now.duration_since(*time) <= age {
+                break;
+            }
+            self.history.pop_front();
+        }
+    }
+
+    /// Количество снимков в истории
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
     /// Добавление метрики
     pub fn add_metric(&self, name: String, value: f64) {
         let mut metrics = self.metrics.lock();
@@ -124,6 +204,112 @@ pub fn check_alerts(&mut self) -> Vec<String> {
     }
 }
 
+impl PrometheusExporter {
+    /// Создание экспортера с пустым реестром
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Регистрация целочисленного счетчика
+    pub fn register_counter(&self, name: &str, help: &str) -> prometheus::Result<IntCounter> {
+        let counter = IntCounter::new(name, help)?;
+        self.registry.register(Box::new(counter.clone()))?;
+        Ok(counter)
+    }
+
+    /// Регистрация датчика
+    pub fn register_gauge(&self, name: &str, help: &str) -> prometheus::Result<Gauge> {
+        let gauge = Gauge::new(name, help)?;
+        self.registry.register(Box::new(gauge.clone()))?;
+        Ok(gauge)
+    }
+
+    /// Регистрация гистограммы с корзинами по умолчанию
+    pub fn register_histogram(&self, name: &str, help: &str) -> prometheus::Result<Histogram> {
+        let histogram = Histogram::with_opts(HistogramOpts::new(name, help))?;
+        self.registry.register(Box::new(histogram.clone()))?;
+        Ok(histogram)
+    }
+
+    /// Все метрики реестра в текстовом формате Prometheus
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("запись в Vec не завершается ошибкой");
+        String::from_utf8(buffer).expect("формат Prometheus текстовый")
+    }
+}
+
+impl SlidingWindowCounter {
+    /// Создание счетчика; паникует при нулевом окне, нулевом числе корзин
+    /// или числе корзин больше `u32::MAX`
+    pub fn new(window: Duration, bucket_count: usize) -> Self {
+        assert!(bucket_count > 0, "нужна хотя бы одна корзина");
+        let bucket_count_u32 = u32::try_from(bucket_count).expect("число корзин больше u32::MAX");
+        let bucket_width = window / bucket_count_u32;
+        assert!(!bucket_width.is_zero(), "окно слишком мало для {} корзин", bucket_count);
+        Self {
+            window,
+            bucket_width,
+            origin: Instant::now(),
+            buckets: VecDeque::with_capacity(bucket_count + 1),
+        }
+    }
+
+    /// Учет события с весом `value` в текущий момент
+    pub fn record(&mut self, value: u64) {
+        self.record_at(value, Instant::now());
+    }
+
+    /// Учет события в момент `at`; устаревшие корзины удаляются
+    pub fn record_at(&mut self, value: u64, at: Instant) {
+        while let Some(&(start, _)) = self.buckets.front() {
+            if self.is_live(start, at) {
+                break;
+            }
+            self.buckets.pop_front();
+        }
+
+        let start = self.bucket_start(at);
+        match self.buckets.back_mut() {
+            Some((last, count)) if *last == start => *count += value,
+            _ => self.buckets.push_back((start, value)),
+        }
+    }
+
+    /// Сумма значений за последнее окно
+    pub fn count_in_window(&self) -> u64 {
+        self.count_in_window_at(Instant::now())
+    }
+
+    /// Сумма значений за окно, заканчивающееся в `now`
+    pub fn count_in_window_at(&self, now: Instant) -> u64 {
+        self.buckets
+            .iter()
+            .filter(|&&(start, _)| self.is_live(start, now))
+            .map(|&(_, count)| count)
+            .sum()
+    }
+
+    /// Начало корзины, в которую попадает момент `at`
+    fn bucket_start(&self, at: Instant) -> Instant {
+        let offset = at.saturating_duration_since(self.origin);
+        let width_nanos = self.bucket_width.as_nanos();
+        let index = offset.as_nanos() / width_nanos;
+        // Произведение не больше offset, поэтому помещается в u64 наносекунд
+        self.origin + Duration::from_nanos((width_nanos * index) as u64)
+    }
+
+    /// Пересекается ли корзина с окном, заканчивающимся в `now`
+    fn is_live(&self, start: Instant, now: Instant) -> bool {
+        match now.checked_sub(self.window) {
+            Some(cutoff) => start + self.bucket_width > cutoff,
+            None => true,
+        }
+    }
+}
+
 /// Демонстрация метрик и мониторинга
 pub fn demonstrate_metrics() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n=== Демонстрация метрик и мониторинга ===");
@@ -156,6 +342,37 @@ pub fn demonstrate_metrics() -> Result<(), Box<dyn std::error::Error>> {
         warn!("{}", alert);
     }
 
+    // Демонстрация истории метрик
+    println!("\n3. История метрик:");
+    for cpu in [50.0, 55.0, 62.0] {
+        monitoring.add_metric("cpu_usage".to_string(), cpu);
+        monitoring.snapshot();
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    if let Some(trend) = monitoring.trend("cpu_usage") {
+        info!("Тренд cpu_usage: {:.1} в секунду", trend);
+    }
+
+    // Демонстрация экспорта в Prometheus
+    println!("\n4. Экспорт Prometheus:");
+    let exporter = PrometheusExporter::new();
+    let requests = exporter.register_counter("demo_requests_total", "Обработанные запросы")?;
+    let temperature = exporter.register_gauge("demo_temperature", "Температура")?;
+    let latency = exporter.register_histogram("demo_latency_seconds", "Время ответа")?;
+    requests.inc_by(3);
+    temperature.set(36.6);
+    latency.observe(0.042);
+    print!("{}", exporter.render());
+
+    // Демонстрация скользящего окна
+    println!("\n5. Скользящее окно:");
+    let mut window = SlidingWindowCounter::new(Duration::from_millis(200), 4);
+    for _ in 0..5 {
+        window.record(1);
+        std::thread::sleep(Duration::from_millis(60));
+    }
+    info!("Событий за последние 200 мс: {}", window.count_in_window());
+
     Ok(())
 }
 
@@ -180,4 +397,95 @@ fn test_monitoring() {
         let alerts = monitoring.check_alerts();
         assert!(!alerts.is_empty());
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_monitoring_trend() {
+        let mut monitoring = MonitoringDemo::new();
+        for i in 0..5 {
+            monitoring.add_metric("cpu_usage".to_string(), 40.0 + 10.0 * i as f64);
+            monitoring.snapshot();
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let trend = monitoring.trend("cpu_usage").unwrap();
+        assert!(trend > 0.0, "тренд {} не положителен", trend);
+        assert_eq!(monitoring.trend("memory_usage"), None);
+    }
+
+    #[test]
+    fn test_monitoring_history_limit_and_prune() {
+        let mut monitoring = MonitoringDemo::with_history_limit(3);
+        monitoring.add_metric("cpu_usage".to_string(), 1.0);
+        for _ in 0..5 {
+            monitoring.snapshot();
+        }
+        assert_eq!(monitoring.history_len(), 3);
+
+        std::thread::sleep(Duration::from_millis(30));
+        monitoring.snapshot();
+        monitoring.prune_older_than(Duration::from_millis(20));
+        assert_eq!(monitoring.history_len(), 1);
+        // Одного снимка недостаточно для тренда
+        assert_eq!(monitoring.trend("cpu_usage"), None);
+    }
+
+    #[test]
+    fn test_prometheus_exporter_render() {
+        let exporter = PrometheusExporter::new();
+        let requests = exporter.register_counter("http_requests_total", "Всего запросов").unwrap();
+        let errors = exporter.register_counter("http_errors_total", "Всего ошибок").unwrap();
+        requests.inc_by(5);
+        errors.inc();
+
+        let output = exporter.render();
+        assert!(output.contains("# TYPE http_requests_total counter"));
+        assert!(output.contains("http_requests_total 5"));
+        assert!(output.contains("http_errors_total 1"));
+
+        // Повторная регистрация имени отклоняется реестром
+        assert!(exporter.register_counter("http_errors_total", "Дубликат").is_err());
+    }
+
+    #[test]
+    fn test_sliding_window_counter() {
+        let mut counter = SlidingWindowCounter::new(Duration::from_secs(1), 10);
+        let start = counter.origin;
+
+        // 100 событий равномерно за 2 секунды
+        for i in 0..100 {
+            counter.record_at(1, start + Duration::from_millis(i * 20));
+        }
+
+        let now = start + Duration::from_secs(2);
+        // В окно (1 с, 2 с] попадают события с 1000 мс по 1980 мс
+        assert_eq!(counter.count_in_window_at(now), 50);
+        // Корзины старше окна удалены при записи
+        assert!(counter.buckets.len() <= 11);
+
+        // Через секунду без событий окно пусто
+        assert_eq!(counter.count_in_window_at(now + Duration::from_secs(1)), 0);
+    }
+
+    #[test]
+    fn test_sliding_window_counter_weights() {
+        let mut counter = SlidingWindowCounter::new(Duration::from_secs(60), 6);
+        counter.record(5);
+        counter.record(7);
+        assert_eq!(counter.count_in_window(), 12);
+    }
+
+    #[test]
+    fn test_sliding_window_counter_many_buckets_elapsed() {
+        // Корзины по 1 нс: через 5 с номер корзины больше u32::MAX
+        let mut counter = SlidingWindowCounter::new(Duration::from_nanos(10), 10);
+        let at = counter.origin + Duration::from_secs(5);
+        counter.record_at(3, at);
+        assert_eq!(counter.count_in_window_at(at), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "больше u32::MAX")]
+    fn test_sliding_window_counter_too_many_buckets() {
+        SlidingWindowCounter::new(Duration::from_secs(1 << 40), u32::MAX as usize + 1);
+    }
+}
@@ -5,39 +5,718 @@
 //! - WebSocket
 //! - TCP/UDP
 //! - Асинхронные сетевые операции
+//! - Разбор HTTP запросов
+//! - Маршрутизация с параметрами пути
+//! - Корректное завершение сервера
+//! - Цепочки middleware
+//! - Пул TCP соединений
+//! - WebSocket handshake и фреймы (RFC 6455)
+//! - UDP multicast
+//! - TLS (rustls)
+//! - Разбор HTTP без копирования (zero-copy)
 
 use tokio::net::{TcpListener, TcpStream, UdpSocket};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use std::collections::{HashMap, VecDeque};
 use std::error::Error;
-use std::net::SocketAddr;
-use tokio::time::{timeout, Duration};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, Weak};
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use rand::Rng;
+use sha1::{Digest, Sha1};
+use socket2::{Domain, Protocol, SockRef, Socket, TcpKeepalive, Type};
+use thiserror::Error;
+use tokio_rustls::TlsAcceptor;
+use tokio::sync::{oneshot, watch, OwnedSemaphorePermit, Semaphore};
+use tokio::time::{timeout, Duration, Instant};
+use crate::metrics::PrometheusExporter;
+
+/// Максимальный размер HTTP запроса
+const MAX_REQUEST_SIZE: usize = 1024 * 1024;
+
+/// GUID из RFC 6455 для вычисления `Sec-WebSocket-Accept`
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Максимальный размер ответа на WebSocket handshake
+const MAX_HANDSHAKE_SIZE: usize = 8 * 1024;
+
+/// Метод HTTP запроса
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HttpMethod {
+    Get,
+    Head,
+    Post,
+    Put,
+    Patch,
+    Delete,
+    Options,
+}
+
+/// Ошибка разбора HTTP запроса
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("Запрос получен не полностью")]
+    Incomplete,
+
+    #[error("Запрос превышает {0} байт")]
+    TooLarge(usize),
+
+    #[error("Заголовки запроса не в UTF-8")]
+    InvalidEncoding,
+
+    #[error("Некорректная строка запроса: {0}")]
+    InvalidRequestLine(String),
+
+    #[error("Некорректная строка статуса: {0}")]
+    InvalidStatusLine(String),
+
+    #[error("Неподдерживаемый метод: {0}")]
+    UnsupportedMethod(String),
+
+    #[error("Некорректный заголовок: {0}")]
+    InvalidHeader(String),
+
+    #[error("Некорректный Content-Length: {0}")]
+    InvalidContentLength(String),
+}
+
+/// Разобранный HTTP запрос
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: HttpMethod,
+    pub path: String,
+    /// Заголовки; имена приведены к нижнему регистру
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+/// HTTP ответ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+/// HTTP запрос, все поля которого заимствованы из входного буфера
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawRequest<'a> {
+    pub method: &'a [u8],
+    pub path: &'a [u8],
+    pub version: &'a [u8],
+    pub headers: Vec<(&'a [u8], &'a [u8])>,
+    pub body: &'a [u8],
+}
+
+impl<'a> RawRequest<'a> {
+    /// Значение заголовка без учета регистра имени
+    pub fn header(&self, name: &str) -> Option<&'a [u8]> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name.as_bytes()))
+            .map(|&(_, value)| value)
+    }
+}
+
+/// Стартовая строка, заголовки и положение тела HTTP сообщения
+struct MessageHead<'a> {
+    start_line: &'a str,
+    headers: HashMap<String, String>,
+    body_start: usize,
+    content_length: Option<usize>,
+}
+
+/// Ошибка HTTP клиента
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("Некорректный URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("Поддерживается только http://, получено: {0}")]
+    UnsupportedScheme(String),
+
+    #[error("Ошибка ввода/вывода: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Превышено время ожидания ответа ({0:?})")]
+    Timeout(Duration),
+
+    #[error("Некорректный ответ: {0}")]
+    InvalidResponse(#[from] ParseError),
+}
+
+/// Минимальный HTTP/1.1 клиент поверх `TcpStream`
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    timeout: Duration,
+}
+
+/// Обработчик запросов HTTP сервера
+pub type RequestHandler = Arc<dyn Fn(HttpRequest) -> HttpResponse + Send + Sync>;
+
+/// Обработчик маршрута; получает запрос и параметры пути
+pub type HandlerFn = Arc<dyn Fn(HttpRequest, HashMap<String, String>) -> HttpResponse + Send + Sync>;
+
+/// Сегмент шаблона маршрута
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    /// Сегмент, совпадающий только с самим собой
+    Static(String),
+    /// Параметр вида `:id`, совпадающий с любым сегментом
+    Param(String),
+}
+
+/// Зарегистрированный маршрут
+struct Route {
+    method: HttpMethod,
+    segments: Vec<PathSegment>,
+    handler: HandlerFn,
+}
+
+/// Маршрутизатор HTTP запросов
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+/// Промежуточный обработчик запросов
+#[async_trait]
+pub trait Middleware: Send + Sync + 'static {
+    /// Обработка запроса; `next` передает запрос дальше по цепочке
+    async fn call(&self, req: HttpRequest, next: Next) -> HttpResponse;
+}
+
+/// Оставшаяся часть цепочки middleware вместе с конечным обработчиком
+pub struct Next {
+    stack: Arc<MiddlewareStack>,
+    index: usize,
+    endpoint: RequestHandler,
+}
+
+/// Цепочка middleware вокруг конечного обработчика
+///
+/// Первый добавленный middleware получает запрос первым.
+#[derive(Default)]
+pub struct MiddlewareStack {
+    middlewares: Vec<Box<dyn Middleware>>,
+}
+
+/// Запись журнала запросов
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestLog {
+    pub method: HttpMethod,
+    pub path: String,
+    pub status: u16,
+    pub elapsed: Duration,
+}
+
+/// Middleware, записывающий метод, путь, код ответа и время обработки
+///
+/// Каждый запрос логируется через `tracing` и сохраняется в журнал `entries`.
+#[derive(Debug, Clone, Default)]
+pub struct LoggingMiddleware {
+    entries: Arc<Mutex<Vec<RequestLog>>>,
+}
+
+/// Middleware, пропускающий только запросы с `Authorization: Bearer <token>`
+#[derive(Debug, Clone)]
+pub struct AuthMiddleware {
+    token: String,
+}
+
+/// Ошибка WebSocket handshake
+#[derive(Debug, Error)]
+pub enum HandshakeError {
+    #[error("Ошибка ввода/вывода: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Сервер закрыл соединение во время handshake")]
+    ConnectionClosed,
+
+    #[error("Некорректный ответ сервера: {0}")]
+    InvalidResponse(String),
+
+    #[error("Сервер не перешел на WebSocket: {0}")]
+    UnexpectedStatus(String),
+
+    #[error("Неверный Sec-WebSocket-Accept: ожидался {expected}, получен {actual:?}")]
+    InvalidAccept {
+        expected: String,
+        actual: Option<String>,
+    },
+}
+
+/// Учет активного соединения: счетчик уменьшается при завершении обработки,
+/// в том числе при панике обработчика
+struct ActiveConnection {
+    active: Arc<watch::Sender<usize>>,
+}
 
 /// Реализация HTTP сервера
 pub struct HttpServer {
     addr: SocketAddr,
+    router: Arc<Router>,
+    handler: RequestHandler,
+    middleware: Arc<MiddlewareStack>,
+    tls: Option<TlsAcceptor>,
+    metrics: Option<Arc<PrometheusExporter>>,
+}
+
+impl HttpMethod {
+    /// Название метода в запросе
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Options => "OPTIONS",
+        }
+    }
+}
+
+impl FromStr for HttpMethod {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "GET" => Ok(HttpMethod::Get),
+            "HEAD" => Ok(HttpMethod::Head),
+            "POST" => Ok(HttpMethod::Post),
+            "PUT" => Ok(HttpMethod::Put),
+            "PATCH" => Ok(HttpMethod::Patch),
+            "DELETE" => Ok(HttpMethod::Delete),
+            "OPTIONS" => Ok(HttpMethod::Options),
+            other => Err(ParseError::UnsupportedMethod(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl HttpRequest {
+    /// Разбор запроса: строка запроса, заголовки и тело длиной `Content-Length`
+    ///
+    /// Возвращает `ParseError::Incomplete`, если данных пока недостаточно.
+    pub fn parse(raw: &[u8]) -> Result<HttpRequest, ParseError> {
+        let head = parse_head(raw)?;
+        let request_line = head.start_line;
+        let mut parts = request_line.split_whitespace();
+        let (method, path, version) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(method), Some(path), Some(version), None) => (method, path, version),
+            _ => return Err(ParseError::InvalidRequestLine(request_line.to_string())),
+        };
+        if !version.starts_with("HTTP/1.") {
+            return Err(ParseError::InvalidRequestLine(request_line.to_string()));
+        }
+        let method = method.parse()?;
+
+        let body_start = head.body_start;
+        let content_length = head.content_length.unwrap_or(0);
+        if raw.len() < body_start + content_length {
+            return Err(ParseError::Incomplete);
+        }
+
+        Ok(HttpRequest {
+            method,
+            path: path.to_string(),
+            headers: head.headers,
+            body: raw[body_start..body_start + content_length].to_vec(),
+        })
+    }
+
+    /// Значение заголовка без учета регистра имени
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+}
+
+impl HttpResponse {
+    /// Разбор ответа; без `Content-Length` телом считается весь остаток
+    pub fn parse(raw: &[u8]) -> Result<HttpResponse, ParseError> {
+        let head = parse_head(raw)?;
+        let status_line = head.start_line;
+        let mut parts = status_line.splitn(3, ' ');
+        let status = match (parts.next(), parts.next()) {
+            (Some(version), Some(status)) if version.starts_with("HTTP/1.") => status
+                .parse::<u16>()
+                .map_err(|_| ParseError::InvalidStatusLine(status_line.to_string()))?,
+            _ => return Err(ParseError::InvalidStatusLine(status_line.to_string())),
+        };
+
+        let body = match head.content_length {
+            Some(length) if raw.len() < head.body_start + length => return Err(ParseError::Incomplete),
+            Some(length) => &raw[head.body_start..head.body_start + length],
+            None => &raw[head.body_start..],
+        };
+
+        Ok(HttpResponse {
+            status,
+            headers: head.headers,
+            body: body.to_vec(),
+        })
+    }
+
+    /// Создание пустого ответа с кодом `status`
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: HashMap::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Ответ 200 OK с телом
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self::new(200).with_body(body)
+    }
+
+    /// Ответ 404 Not Found
+    pub fn not_found() -> Self {
+        Self::new(404).with_body("Not Found")
+    }
+
+    /// Установка тела ответа
+    pub fn with_body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Добавление заголовка
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(name.to_ascii_lowercase(), value.to_string());
+        self
+    }
+
+    /// Сериализация ответа в формат HTTP/1.1
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason_phrase(self.status));
+        for (name, value) in &self.headers {
+            if name != "content-length" {
+                head.push_str(&format!("{}: {}\r\n", name, value));
+            }
+        }
+        head.push_str(&format!("content-length: {}\r\n\r\n", self.body.len()));
+
+        let mut bytes = head.into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
+
+impl Router {
+    /// Создание пустого маршрутизатора
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Регистрация маршрута; сегменты вида `:name` становятся параметрами
+    pub fn add_route(&mut self, method: HttpMethod, pattern: &str, handler: HandlerFn) {
+        let segments = split_path(pattern)
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => PathSegment::Param(name.to_string()),
+                None => PathSegment::Static(segment.to_string()),
+            })
+            .collect();
+        self.routes.push(Route {
+            method,
+            segments,
+            handler,
+        });
+    }
+
+    /// Поиск маршрута для запроса; маршруты проверяются в порядке регистрации
+    pub fn route(&self, request: &HttpRequest) -> Option<(HandlerFn, HashMap<String, String>)> {
+        let path: Vec<&str> = split_path(&request.path).collect();
+        self.routes
+            .iter()
+            .filter(|route| route.method == request.method && route.segments.len() == path.len())
+            .find_map(|route| {
+                let mut params = HashMap::new();
+                for (segment, value) in route.segments.iter().zip(&path) {
+                    match segment {
+                        PathSegment::Static(expected) if expected == value => {}
+                        PathSegment::Static(_) => return None,
+                        PathSegment::Param(name) => {
+                            params.insert(name.clone(), value.to_string());
+                        }
+                    }
+                }
+                Some((Arc::clone(&route.handler), params))
+            })
+    }
+}
+
+impl Next {
+    /// Передача запроса следующему middleware или конечному обработчику
+    pub async fn run(self, req: HttpRequest) -> HttpResponse {
+        let stack = Arc::clone(&self.stack);
+        match stack.middlewares.get(self.index) {
+            Some(middleware) => {
+                let next = Next {
+                    stack: Arc::clone(&self.stack),
+                    index: self.index + 1,
+                    endpoint: self.endpoint,
+                };
+                middleware.call(req, next).await
+            }
+            None => (self.endpoint)(req),
+        }
+    }
+}
+
+impl MiddlewareStack {
+    /// Создание пустой цепочки
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Добавление middleware в конец цепочки
+    pub fn with<M: Middleware>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Box::new(middleware));
+        self
+    }
+
+    /// Проведение запроса через всю цепочку до `endpoint`
+    pub async fn handle(self: Arc<Self>, req: HttpRequest, endpoint: RequestHandler) -> HttpResponse {
+        Next {
+            stack: self,
+            index: 0,
+            endpoint,
+        }
+        .run(req)
+        .await
+    }
+}
+
+impl LoggingMiddleware {
+    /// Создание middleware с пустым журналом
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Копия записей журнала
+    pub fn entries(&self) -> Vec<RequestLog> {
+        self.entries.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Middleware for LoggingMiddleware {
+    async fn call(&self, req: HttpRequest, next: Next) -> HttpResponse {
+        let (method, path) = (req.method, req.path.clone());
+        let start = Instant::now();
+        let response = next.run(req).await;
+        let entry = RequestLog {
+            method,
+            path,
+            status: response.status,
+            elapsed: start.elapsed(),
+        };
+
+        tracing::info!(
+            method = %entry.method,
+            path = %entry.path,
+            status = entry.status,
+            elapsed = ?entry.elapsed,
+            "HTTP запрос обработан"
+        );
+        self.entries.lock().unwrap().push(entry);
+        response
+    }
+}
+
+impl AuthMiddleware {
+    /// Создание middleware с ожидаемым токеном
+    pub fn new(token: impl Into<String>) -> Self {
+        Self { token: token.into() }
+    }
+}
+
+#[async_trait]
+impl Middleware for AuthMiddleware {
+    async fn call(&self, req: HttpRequest, next: Next) -> HttpResponse {
+        let authorized = req
+            .header("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| token == self.token);
+
+        if authorized {
+            next.run(req).await
+        } else {
+            HttpResponse::new(401)
+                .with_header("www-authenticate", "Bearer")
+                .with_body("Unauthorized")
+        }
+    }
 }
 
 impl HttpServer {
     /// Создание нового HTTP сервера
     pub fn new(addr: SocketAddr) -> Self {
-        Self { addr }
+        Self {
+            addr,
+            router: Arc::new(Router::new()),
+            handler: Arc::new(|_| HttpResponse::not_found()),
+            middleware: Arc::new(MiddlewareStack::new()),
+            tls: None,
+            metrics: None,
+        }
+    }
+
+    /// Создание HTTPS сервера с цепочкой сертификатов и закрытым ключом в PEM
+    pub fn new_tls(addr: SocketAddr, cert_path: &Path, key_path: &Path) -> Result<HttpServer, Box<dyn Error>> {
+        let certs = rustls_pemfile::certs(&mut BufReader::new(File::open(cert_path)?))?
+            .into_iter()
+            .map(rustls::Certificate)
+            .collect::<Vec<_>>();
+        if certs.is_empty() {
+            return Err(format!("в {} нет сертификатов", cert_path.display()).into());
+        }
+
+        let key = rustls_pemfile::read_all(&mut BufReader::new(File::open(key_path)?))?
+            .into_iter()
+            .find_map(|item| match item {
+                rustls_pemfile::Item::PKCS8Key(key)
+                | rustls_pemfile::Item::RSAKey(key)
+                | rustls_pemfile::Item::ECKey(key) => Some(rustls::PrivateKey(key)),
+                _ => None,
+            })
+            .ok_or_else(|| format!("в {} нет закрытого ключа", key_path.display()))?;
+
+        let config = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)?;
+
+        let mut server = Self::new(addr);
+        server.tls = Some(TlsAcceptor::from(Arc::new(config)));
+        Ok(server)
+    }
+
+    /// Установка цепочки middleware, через которую проходят все запросы
+    pub fn with_middleware(mut self, middleware: MiddlewareStack) -> Self {
+        self.middleware = Arc::new(middleware);
+        self
+    }
+
+    /// Установка маршрутизатора
+    pub fn with_router(mut self, router: Router) -> Self {
+        self.router = Arc::new(router);
+        self
+    }
+
+    /// Установка обработчика запросов, не подошедших ни к одному маршруту
+    ///
+    /// По умолчанию сервер отвечает 404.
+    pub fn with_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    {
+        self.handler = Arc::new(handler);
+        self
+    }
+
+    /// Включение эндпоинта `GET /metrics` с метриками экспортера
+    pub fn with_metrics(mut self, exporter: Arc<PrometheusExporter>) -> Self {
+        self.metrics = Some(exporter);
+        self
+    }
+
+    /// Обработчик, который сначала ищет маршрут, а затем вызывает запасной
+    fn dispatcher(&self) -> RequestHandler {
+        let router = Arc::clone(&self.router);
+        let fallback = Arc::clone(&self.handler);
+        let metrics = self.metrics.clone();
+        Arc::new(move |request| {
+            if let Some(exporter) = &metrics {
+                if request.method == HttpMethod::Get && split_path(&request.path).eq(["metrics"]) {
+                    return HttpResponse::ok(exporter.render())
+                        .with_header("Content-Type", "text/plain; version=0.0.4");
+                }
+            }
+            match router.route(&request) {
+                Some((handler, params)) => handler(request, params),
+                None => fallback(request),
+            }
+        })
     }
 
     /// Запуск сервера
     pub async fn run(&self) -> Result<(), Box<dyn Error>> {
+        // Отправитель живет до конца работы, поэтому сигнал не придет никогда
+        let (_shutdown_tx, shutdown_rx) = oneshot::channel();
+        self.run_with_shutdown(shutdown_rx).await
+    }
+
+    /// Запуск сервера до получения сигнала завершения
+    ///
+    /// После сигнала сервер перестает принимать подключения и дожидается
+    /// завершения уже начатых обработчиков.
+    pub async fn run_with_shutdown(&self, mut shutdown: oneshot::Receiver<()>) -> Result<(), Box<dyn Error>> {
         let listener = TcpListener::bind(self.addr).await?;
         println!("HTTP сервер запущен на {}", self.addr);
 
+        let dispatcher = self.dispatcher();
+        let (active, mut active_count) = watch::channel(0usize);
+        let active = Arc::new(active);
         loop {
-            let (socket, addr) = listener.accept().await?;
+            let (socket, addr) = tokio::select! {
+                accepted = listener.accept() => accepted?,
+                _ = &mut shutdown => break,
+            };
             println!("Новое подключение от {}", addr);
             
+            let handler = Arc::clone(&dispatcher);
+            let middleware = Arc::clone(&self.middleware);
+            let tls = self.tls.clone();
+            let connection = ActiveConnection::new(Arc::clone(&active));
             tokio::spawn(async move {
-                if let Err(e) = handle_connection(socket).await {
+                let _connection = connection;
+                let result = match tls {
+                    Some(acceptor) => match acceptor.accept(socket).await {
+                        Ok(stream) => handle_connection(stream, middleware, handler).await,
+                        Err(e) => Err(e.into()),
+                    },
+                    None => handle_connection(socket, middleware, handler).await,
+                };
+                if let Err(e) = result {
                     eprintln!("Ошибка обработки соединения: {}", e);
                 }
             });
         }
+
+        drop(listener);
+        println!("HTTP сервер завершает работу, ожидание активных соединений");
+        active_count.wait_for(|&count| count == 0).await?;
+        Ok(())
+    }
+}
+
+impl ActiveConnection {
+    fn new(active: Arc<watch::Sender<usize>>) -> Self {
+        active.send_modify(|count| *count += 1);
+        Self { active }
+    }
+}
+
+impl Drop for ActiveConnection {
+    fn drop(&mut self) {
+        self.active.send_modify(|count| *count -= 1);
     }
 }
 
@@ -59,6 +738,85 @@ pub async fn connect(&self) -> Result<TcpStream, Box<dyn Error>> {
         Ok(stream)
     }
 
+    /// WebSocket handshake: HTTP Upgrade с проверкой `Sec-WebSocket-Accept`
+    pub async fn handshake(&self, stream: &mut TcpStream, path: &str) -> Result<(), HandshakeError> {
+        let key = BASE64.encode(rand::thread_rng().gen::<[u8; 16]>());
+        let request = format!(
+            "GET {} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\n\r\n",
+            path, self.addr, key
+        );
+        stream.write_all(request.as_bytes()).await?;
+
+        // Читаем по байту, чтобы не захватить фреймы, идущие сразу за заголовками
+        let mut response = Vec::new();
+        while !response.ends_with(b"\r\n\r\n") {
+            if response.len() >= MAX_HANDSHAKE_SIZE {
+                return Err(HandshakeError::InvalidResponse("слишком длинные заголовки".to_string()));
+            }
+            match stream.read_u8().await {
+                Ok(byte) => response.push(byte),
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Err(HandshakeError::ConnectionClosed),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let response = std::str::from_utf8(&response)
+            .map_err(|_| HandshakeError::InvalidResponse("заголовки не в UTF-8".to_string()))?;
+        let mut lines = response.split("\r\n");
+        let status_line = lines.next().unwrap_or_default();
+        if status_line.split_whitespace().nth(1) != Some("101") {
+            return Err(HandshakeError::UnexpectedStatus(status_line.to_string()));
+        }
+
+        let headers: HashMap<String, String> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+            .collect();
+        if !headers.get("upgrade").is_some_and(|value| value.eq_ignore_ascii_case("websocket")) {
+            return Err(HandshakeError::InvalidResponse("нет заголовка Upgrade: websocket".to_string()));
+        }
+
+        let expected = websocket_accept_key(&key);
+        let actual = headers.get("sec-websocket-accept");
+        if actual != Some(&expected) {
+            return Err(HandshakeError::InvalidAccept {
+                expected,
+                actual: actual.cloned(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Отправка текстового фрейма с маскированием, обязательным для клиента
+    pub async fn send_text_frame(&self, stream: &mut TcpStream, text: &str) -> io::Result<()> {
+        let payload = text.as_bytes();
+        // FIN = 1, opcode = 0x1 (текст)
+        let mut frame = vec![0x81];
+        match payload.len() {
+            len @ 0..=125 => frame.push(0x80 | len as u8),
+            len @ 126..=0xFFFF => {
+                frame.push(0x80 | 126);
+                frame.extend_from_slice(&(len as u16).to_be_bytes());
+            }
+            len => {
+                frame.push(0x80 | 127);
+                frame.extend_from_slice(&(len as u64).to_be_bytes());
+            }
+        }
+
+        let mask: [u8; 4] = rand::thread_rng().gen();
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().zip(mask.iter().cycle()).map(|(byte, mask)| byte ^ mask));
+
+        stream.write_all(&frame).await
+    }
+
+    /// Создание пула соединений к серверу
+    pub fn connection_pool(&self, max_size: usize, keepalive_interval: Duration) -> ConnectionPool {
+        ConnectionPool::new(self.addr, max_size, keepalive_interval)
+    }
+
     /// Отправка сообщения
     pub async fn send_message(&self, stream: &mut TcpStream, message: &str) -> Result<(), Box<dyn Error>> {
         stream.write_all(message.as_bytes()).await?;
@@ -73,20 +831,288 @@ pub async fn receive_message(&self, stream: &mut TcpStream) -> Result<String, Bo
     }
 }
 
+/// Пул TCP соединений, сгруппированных по адресу сервера
+///
+/// Одновременно выдается не больше `max_size` соединений; возвращенное
+/// соединение переиспользуется только для своего адреса.
+///
+/// Heartbeat: каждому сокету включаются TCP keep-alive пробы с периодом
+/// `keepalive_interval` (не меньше секунды). Пробы отправляет ядро, поэтому
+/// они не вмешиваются в прикладной протокол поверх соединения. Фоновая
+/// задача с тем же периодом удаляет соединения, закрытые сервером или
+/// оборванные после безответных проб.
+#[derive(Clone)]
+pub struct ConnectionPool {
+    inner: Arc<PoolInner>,
+}
+
+/// Общее состояние пула
+struct PoolInner {
+    /// Адрес для `acquire` без явного адреса
+    addr: SocketAddr,
+    keepalive_interval: Duration,
+    idle: tokio::sync::Mutex<HashMap<SocketAddr, VecDeque<TcpStream>>>,
+    permits: Arc<Semaphore>,
+}
+
+/// Соединение, взятое из пула; при удалении возвращается обратно
+pub struct PooledStream {
+    stream: Option<TcpStream>,
+    addr: SocketAddr,
+    pool: Arc<PoolInner>,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl ConnectionPool {
+    /// Создание пула с адресом по умолчанию; должно вызываться внутри среды выполнения Tokio
+    pub fn new(addr: SocketAddr, max_size: usize, keepalive_interval: Duration) -> Self {
+        let inner = Arc::new(PoolInner {
+            addr,
+            keepalive_interval,
+            idle: tokio::sync::Mutex::new(HashMap::new()),
+            permits: Arc::new(Semaphore::new(max_size)),
+        });
+        tokio::spawn(keepalive(Arc::downgrade(&inner), keepalive_interval));
+        Self { inner }
+    }
+
+    /// Получение соединения к адресу по умолчанию
+    pub async fn acquire(&self) -> Result<PooledStream, io::Error> {
+        self.acquire_to(self.inner.addr).await
+    }
+
+    /// Получение соединения к `addr`; ждет, если все `max_size` соединений заняты
+    pub async fn acquire_to(&self, addr: SocketAddr) -> Result<PooledStream, io::Error> {
+        let permit = Arc::clone(&self.inner.permits)
+            .acquire_owned()
+            .await
+            .expect("семафор пула не закрывается");
+
+        let idle = self.inner.idle.lock().await.get_mut(&addr).and_then(VecDeque::pop_front);
+        let stream = match idle {
+            Some(stream) => stream,
+            None => {
+                let stream = TcpStream::connect(addr).await?;
+                // TCP_KEEPIDLE задается в целых секундах, ноль ядро отвергает
+                let period = self.inner.keepalive_interval.max(Duration::from_secs(1));
+                let keepalive = TcpKeepalive::new().with_time(period).with_interval(period);
+                SockRef::from(&stream).set_tcp_keepalive(&keepalive)?;
+                stream
+            }
+        };
+
+        Ok(PooledStream {
+            stream: Some(stream),
+            addr,
+            pool: Arc::clone(&self.inner),
+            permit: Some(permit),
+        })
+    }
+
+    /// Количество простаивающих соединений по всем адресам
+    pub async fn idle_count(&self) -> usize {
+        self.inner.idle.lock().await.values().map(VecDeque::len).sum()
+    }
+}
+
+/// Периодическая проверка простаивающих соединений
+async fn keepalive(pool: Weak<PoolInner>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await;
+    loop {
+        ticker.tick().await;
+        let Some(pool) = pool.upgrade() else { break };
+
+        let mut idle = pool.idle.lock().await;
+        let mut buf = [0u8; 1];
+        // Простаивающее соединение не должно получать данные: EOF, ошибка
+        // после безответных keep-alive проб или неожиданные байты означают,
+        // что его нельзя переиспользовать
+        idle.retain(|_, streams| {
+            streams.retain(|stream| {
+                matches!(stream.try_read(&mut buf), Err(e) if e.kind() == io::ErrorKind::WouldBlock)
+            });
+            !streams.is_empty()
+        });
+    }
+}
+
+impl Deref for PooledStream {
+    type Target = TcpStream;
+
+    fn deref(&self) -> &TcpStream {
+        self.stream.as_ref().expect("соединение уже возвращено в пул")
+    }
+}
+
+impl DerefMut for PooledStream {
+    fn deref_mut(&mut self) -> &mut TcpStream {
+        self.stream.as_mut().expect("соединение уже возвращено в пул")
+    }
+}
+
+impl Drop for PooledStream {
+    fn drop(&mut self) {
+        let (Some(stream), Some(permit)) = (self.stream.take(), self.permit.take()) else {
+            return;
+        };
+
+        // Разрешение освобождается только после возврата соединения, чтобы
+        // следующий `acquire` его переиспользовал
+        let addr = self.addr;
+        if let Ok(mut idle) = self.pool.idle.try_lock() {
+            idle.entry(addr).or_default().push_back(stream);
+            drop(permit);
+        } else if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let pool = Arc::clone(&self.pool);
+            runtime.spawn(async move {
+                pool.idle.lock().await.entry(addr).or_default().push_back(stream);
+                drop(permit);
+            });
+        }
+    }
+}
+
+impl HttpClient {
+    /// Создание клиента с таймаутом 30 секунд
+    pub fn new() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Установка таймаута на весь обмен: подключение, запрос и ответ
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// GET запрос
+    pub async fn get(&self, url: &str) -> Result<HttpResponse, ClientError> {
+        self.request(HttpMethod::Get, url, &[]).await
+    }
+
+    /// POST запрос с телом
+    pub async fn post(&self, url: &str, body: &[u8]) -> Result<HttpResponse, ClientError> {
+        self.request(HttpMethod::Post, url, body).await
+    }
+
+    /// Выполнение запроса; соединение закрывается после ответа
+    pub async fn request(&self, method: HttpMethod, url: &str, body: &[u8]) -> Result<HttpResponse, ClientError> {
+        let (host, port, path) = parse_url(url)?;
+        let exchange = async {
+            let mut stream = TcpStream::connect((host, port)).await?;
+
+            let mut request = format!(
+                "{} {} HTTP/1.1\r\nHost: {}:{}\r\nConnection: close\r\nContent-Length: {}\r\n\r\n",
+                method,
+                path,
+                host,
+                port,
+                body.len()
+            )
+            .into_bytes();
+            request.extend_from_slice(body);
+            stream.write_all(&request).await?;
+
+            let mut raw = Vec::new();
+            stream.read_to_end(&mut raw).await?;
+            Ok::<_, ClientError>(HttpResponse::parse(&raw)?)
+        };
+
+        timeout(self.timeout, exchange)
+            .await
+            .map_err(|_| ClientError::Timeout(self.timeout))?
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Разбор URL вида `http://host[:port][/path]`
+fn parse_url(url: &str) -> Result<(&str, u16, &str), ClientError> {
+    let rest = match url.split_once("://") {
+        Some(("http", rest)) => rest,
+        Some((scheme, _)) => return Err(ClientError::UnsupportedScheme(scheme.to_string())),
+        None => return Err(ClientError::InvalidUrl(url.to_string())),
+    };
+
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => {
+            let port = port.parse().map_err(|_| ClientError::InvalidUrl(url.to_string()))?;
+            (host, port)
+        }
+        None => (authority, 80),
+    };
+    if host.is_empty() {
+        return Err(ClientError::InvalidUrl(url.to_string()));
+    }
+    Ok((host, port, path))
+}
+
 /// Реализация UDP сервера
 pub struct UdpServer {
     addr: SocketAddr,
+    socket: Option<UdpSocket>,
+}
+
+/// Отправитель датаграмм в multicast группу
+pub struct UdpMulticastSender {
+    socket: UdpSocket,
+    group: SocketAddrV4,
 }
 
 impl UdpServer {
     /// Создание нового UDP сервера
     pub fn new(addr: SocketAddr) -> Self {
-        Self { addr }
+        Self { addr, socket: None }
+    }
+
+    /// Привязка сокета с `SO_REUSEADDR`, чтобы несколько получателей
+    /// multicast группы могли слушать один порт
+    ///
+    /// Должна вызываться внутри среды выполнения Tokio.
+    pub fn bind(mut self) -> io::Result<Self> {
+        let socket = Socket::new(Domain::for_address(self.addr), Type::DGRAM, Some(Protocol::UDP))?;
+        socket.set_reuse_address(true)?;
+        socket.set_nonblocking(true)?;
+        socket.bind(&self.addr.into())?;
+        self.socket = Some(UdpSocket::from_std(socket.into())?);
+        Ok(self)
+    }
+
+    /// Вступление в multicast группу на указанном интерфейсе
+    pub fn join_multicast_group(&self, multicast_addr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        SockRef::from(self.bound_socket()?).join_multicast_v4(&multicast_addr, &interface)
+    }
+
+    /// Выход из multicast группы
+    pub fn leave_multicast_group(&self, multicast_addr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        SockRef::from(self.bound_socket()?).leave_multicast_v4(&multicast_addr, &interface)
+    }
+
+    /// Получение одной датаграммы привязанным сокетом
+    pub async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        self.bound_socket()?.recv_from(buf).await
     }
 
     /// Запуск сервера
     pub async fn run(&self) -> Result<(), Box<dyn Error>> {
-        let socket = UdpSocket::bind(self.addr).await?;
+        let bound;
+        let socket = match &self.socket {
+            Some(socket) => socket,
+            None => {
+                bound = UdpSocket::bind(self.addr).await?;
+                &bound
+            }
+        };
         println!("UDP сервер запущен на {}", self.addr);
 
         let mut buf = [0; 1024];
@@ -98,27 +1124,246 @@ pub async fn run(&self) -> Result<(), Box<dyn Error>> {
             socket.send_to(&buf[..size], addr).await?;
         }
     }
+
+    fn bound_socket(&self) -> io::Result<&UdpSocket> {
+        self.socket
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "сокет не привязан, вызовите bind"))
+    }
 }
 
-/// Обработка HTTP соединения
-async fn handle_connection(mut socket: TcpStream) -> Result<(), Box<dyn Error>> {
-    let mut buffer = [0; 1024];
-    let n = socket.read(&mut buffer).await?;
-    
-    let request = String::from_utf8_lossy(&buffer[..n]);
-    println!("Получен запрос:\n{}", request);
+impl UdpMulticastSender {
+    /// Создание отправителя, рассылающего через интерфейс `interface`
+    pub async fn new(group: SocketAddrV4, interface: Ipv4Addr) -> io::Result<Self> {
+        let socket = UdpSocket::bind(SocketAddrV4::new(interface, 0)).await?;
+        let socket_ref = SockRef::from(&socket);
+        socket_ref.set_multicast_if_v4(&interface)?;
+        // Датаграммы доставляются и получателям на этом же узле
+        socket_ref.set_multicast_loop_v4(true)?;
+        socket_ref.set_multicast_ttl_v4(1)?;
+        Ok(Self { socket, group })
+    }
 
-    // Простой HTTP ответ
-    let response = "HTTP/1.1 200 OK\r\nContent-Length: 12\r\n\r\nHello, World!";
-    socket.write_all(response.as_bytes()).await?;
+    /// Отправка датаграммы в группу
+    pub async fn send(&self, data: &[u8]) -> io::Result<usize> {
+        self.socket.send_to(data, self.group).await
+    }
+}
+
+/// Обработка HTTP соединения
+async fn handle_connection<S>(
+    mut socket: S,
+    middleware: Arc<MiddlewareStack>,
+    handler: RequestHandler,
+) -> Result<(), Box<dyn Error>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let request = read_request(&mut socket).await?;
+    let response = match request {
+        Ok(request) => {
+            println!("Получен запрос: {} {}", request.method, request.path);
+            middleware.handle(request, handler).await
+        }
+        Err(e) => HttpResponse::new(400).with_body(e.to_string()),
+    };
 
+    socket.write_all(&response.to_bytes()).await?;
+    // Для TLS отправляет close_notify перед закрытием соединения
+    socket.shutdown().await?;
     Ok(())
 }
 
+/// Чтение запроса из сокета до получения заголовков и всего тела
+async fn read_request<S>(socket: &mut S) -> Result<Result<HttpRequest, ParseError>, Box<dyn Error>>
+where
+    S: AsyncRead + Unpin,
+{
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 1024];
+    loop {
+        let n = socket.read(&mut chunk).await?;
+        if n == 0 {
+            return Err("соединение закрыто до получения полного запроса".into());
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+
+        match HttpRequest::parse(&buffer) {
+            Err(ParseError::Incomplete) => continue,
+            result => return Ok(result),
+        }
+    }
+}
+
+/// Разбор запроса на срезах входного буфера, без копирования строк
+///
+/// В отличие от `HttpRequest::parse` не проверяет UTF-8 и не приводит
+/// имена заголовков к нижнему регистру. Память выделяется только под
+/// список заголовков.
+pub fn parse_raw(buf: &[u8]) -> Result<RawRequest<'_>, ParseError> {
+    let head_end = match buf.windows(4).position(|w| w == b"\r\n\r\n") {
+        Some(end) => end,
+        None if buf.len() > MAX_REQUEST_SIZE => return Err(ParseError::TooLarge(MAX_REQUEST_SIZE)),
+        None => return Err(ParseError::Incomplete),
+    };
+    let mut lines = split_crlf(&buf[..head_end]);
+    let request_line = lines.next().unwrap_or_default();
+
+    let mut parts = request_line.split(|&byte| byte == b' ');
+    let (method, path, version) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(path), Some(version), None)
+            if !method.is_empty() && !path.is_empty() && version.starts_with(b"HTTP/1.") =>
+        {
+            (method, path, version)
+        }
+        _ => {
+            return Err(ParseError::InvalidRequestLine(
+                String::from_utf8_lossy(request_line).into_owned(),
+            ))
+        }
+    };
+
+    let mut headers = Vec::new();
+    let mut content_length = 0;
+    for line in lines {
+        let colon = line
+            .iter()
+            .position(|&byte| byte == b':')
+            .ok_or_else(|| ParseError::InvalidHeader(String::from_utf8_lossy(line).into_owned()))?;
+        let name = line[..colon].trim_ascii();
+        let value = line[colon + 1..].trim_ascii();
+        if name.eq_ignore_ascii_case(b"content-length") {
+            content_length = std::str::from_utf8(value)
+                .ok()
+                .and_then(|value| value.parse::<usize>().ok())
+                .ok_or_else(|| {
+                    ParseError::InvalidContentLength(String::from_utf8_lossy(value).into_owned())
+                })?;
+        }
+        headers.push((name, value));
+    }
+
+    let body_start = head_end + 4;
+    let body_end = match body_start.checked_add(content_length) {
+        Some(end) if end <= MAX_REQUEST_SIZE => end,
+        _ => return Err(ParseError::TooLarge(MAX_REQUEST_SIZE)),
+    };
+    if buf.len() < body_end {
+        return Err(ParseError::Incomplete);
+    }
+
+    Ok(RawRequest {
+        method,
+        path,
+        version,
+        headers,
+        body: &buf[body_start..body_end],
+    })
+}
+
+/// Строки, разделенные `\r\n`
+fn split_crlf(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut rest = Some(data);
+    std::iter::from_fn(move || {
+        let current = rest?;
+        match current.windows(2).position(|w| w == b"\r\n") {
+            Some(end) => {
+                rest = Some(&current[end + 2..]);
+                Some(&current[..end])
+            }
+            None => {
+                rest = None;
+                Some(current)
+            }
+        }
+    })
+}
+
+/// Разбор стартовой строки и заголовков HTTP сообщения
+fn parse_head(raw: &[u8]) -> Result<MessageHead<'_>, ParseError> {
+    let head_end = match raw.windows(4).position(|w| w == b"\r\n\r\n") {
+        Some(end) => end,
+        None if raw.len() > MAX_REQUEST_SIZE => return Err(ParseError::TooLarge(MAX_REQUEST_SIZE)),
+        None => return Err(ParseError::Incomplete),
+    };
+    let head = std::str::from_utf8(&raw[..head_end]).map_err(|_| ParseError::InvalidEncoding)?;
+    let mut lines = head.split("\r\n");
+    let start_line = lines.next().unwrap_or_default();
+
+    let mut headers = HashMap::new();
+    for line in lines {
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::InvalidHeader(line.to_string()))?;
+        headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+    }
+
+    let content_length = match headers.get("content-length") {
+        Some(value) => Some(
+            value
+                .parse::<usize>()
+                .map_err(|_| ParseError::InvalidContentLength(value.clone()))?,
+        ),
+        None => None,
+    };
+    let body_start = head_end + 4;
+    // Content-Length близкий к usize::MAX переполнил бы сложение (найдено фаззингом)
+    if body_start.saturating_add(content_length.unwrap_or(0)) > MAX_REQUEST_SIZE {
+        return Err(ParseError::TooLarge(MAX_REQUEST_SIZE));
+    }
+
+    Ok(MessageHead {
+        start_line,
+        headers,
+        body_start,
+        content_length,
+    })
+}
+
+/// Значение `Sec-WebSocket-Accept` для ключа клиента
+fn websocket_accept_key(key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(WEBSOCKET_GUID.as_bytes());
+    BASE64.encode(hasher.finalize())
+}
+
+/// Непустые сегменты пути без строки запроса
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+/// Текстовое описание кода ответа
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        101 => "Switching Protocols",
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
 /// Демонстрация HTTP сервера
 pub async fn demonstrate_http_server() -> Result<(), Box<dyn Error>> {
     let addr = "127.0.0.1:8080".parse()?;
-    let server = HttpServer::new(addr);
+    let mut router = Router::new();
+    router.add_route(HttpMethod::Get, "/", Arc::new(|_, _| HttpResponse::ok("Hello, World!")));
+    router.add_route(
+        HttpMethod::Get,
+        "/users/:id",
+        Arc::new(|_, params| HttpResponse::ok(format!("Пользователь {}", params["id"]))),
+    );
+    let server = HttpServer::new(addr).with_router(router);
     server.run().await
 }
 
@@ -200,4 +1445,582 @@ async fn test_udp_server() {
         // Отменяем сервер
         server_handle.abort();
     }
+
+    #[test]
+    fn test_parse_get_request() {
+        let raw = b"GET /users?page=2 HTTP/1.1\r\nHost: localhost\r\nAccept: */*\r\n\r\n";
+        let request = HttpRequest::parse(raw).unwrap();
+
+        assert_eq!(request.method, HttpMethod::Get);
+        assert_eq!(request.path, "/users?page=2");
+        assert_eq!(request.header("Host"), Some("localhost"));
+        assert_eq!(request.header("accept"), Some("*/*"));
+        assert!(request.body.is_empty());
+    }
+
+    #[test]
+    fn test_parse_post_request_with_json_body() {
+        let body = br#"{"name":"Alice","age":30}"#;
+        let mut raw = format!(
+            "POST /users HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        raw.extend_from_slice(body);
+        // Лишние байты после Content-Length не попадают в тело
+        raw.extend_from_slice(b"GET /next");
+
+        let request = HttpRequest::parse(&raw).unwrap();
+        assert_eq!(request.method, HttpMethod::Post);
+        assert_eq!(request.header("content-type"), Some("application/json"));
+        assert_eq!(request.body, body);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(HttpRequest::parse(b"GET / HTTP/1.1\r\nHost: x"), Err(ParseError::Incomplete));
+        assert_eq!(
+            HttpRequest::parse(b"POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nshort"),
+            Err(ParseError::Incomplete)
+        );
+        assert_eq!(
+            HttpRequest::parse(b"BREW /pot HTTP/1.1\r\n\r\n"),
+            Err(ParseError::UnsupportedMethod("BREW".to_string()))
+        );
+        assert!(matches!(
+            HttpRequest::parse(b"GET /\r\n\r\n"),
+            Err(ParseError::InvalidRequestLine(_))
+        ));
+        assert!(matches!(
+            HttpRequest::parse(b"GET / HTTP/1.1\r\nno-colon\r\n\r\n"),
+            Err(ParseError::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            HttpRequest::parse(b"GET / HTTP/1.1\r\nContent-Length: abc\r\n\r\n"),
+            Err(ParseError::InvalidContentLength(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_huge_content_length() {
+        // Регрессия: паника от переполнения, найденная fuzz/fuzz_targets/parse_http_request.rs
+        let raw = format!("GET / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", usize::MAX);
+        assert_eq!(HttpRequest::parse(raw.as_bytes()), Err(ParseError::TooLarge(MAX_REQUEST_SIZE)));
+
+        let raw = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", usize::MAX - 3);
+        assert_eq!(HttpResponse::parse(raw.as_bytes()), Err(ParseError::TooLarge(MAX_REQUEST_SIZE)));
+
+        let raw = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", usize::MAX);
+        assert!(matches!(parse_raw(raw.as_bytes()), Err(ParseError::TooLarge(MAX_REQUEST_SIZE))));
+    }
+
+    #[tokio::test]
+    async fn test_http_server_handler() {
+        let addr: SocketAddr = "127.0.0.1:8086".parse().unwrap();
+        let server = HttpServer::new(addr).with_handler(|request| {
+            HttpResponse::ok(format!("{} {} {}", request.method, request.path, request.body.len()))
+        });
+        let server_handle = tokio::spawn(async move { server.run().await.map_err(|e| e.to_string()) });
+        sleep(Duration::from_millis(100)).await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"POST /echo HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("\r\n\r\nPOST /echo 5"));
+        server_handle.abort();
+    }
+
+    fn request(method: HttpMethod, path: &str) -> HttpRequest {
+        HttpRequest {
+            method,
+            path: path.to_string(),
+            headers: HashMap::new(),
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_router_path_params() {
+        let mut router = Router::new();
+        router.add_route(HttpMethod::Get, "/users/:id", Arc::new(|_, _| HttpResponse::ok("user")));
+        router.add_route(HttpMethod::Get, "/users/:id/posts", Arc::new(|_, _| HttpResponse::ok("posts")));
+        router.add_route(HttpMethod::Delete, "/users/:id", Arc::new(|_, _| HttpResponse::new(204)));
+
+        let (handler, params) = router.route(&request(HttpMethod::Get, "/users/42/posts")).unwrap();
+        assert_eq!(params.get("id").map(String::as_str), Some("42"));
+        let req = request(HttpMethod::Get, "/users/42/posts");
+        assert_eq!(handler(req, params).body, b"posts");
+
+        let (handler, params) = router.route(&request(HttpMethod::Get, "/users/7?full=1")).unwrap();
+        assert_eq!(params["id"], "7");
+        assert_eq!(handler(request(HttpMethod::Get, "/users/7"), params).body, b"user");
+
+        let (handler, _) = router.route(&request(HttpMethod::Delete, "/users/7")).unwrap();
+        assert_eq!(handler(request(HttpMethod::Delete, "/users/7"), HashMap::new()).status, 204);
+
+        assert!(router.route(&request(HttpMethod::Get, "/users")).is_none());
+        assert!(router.route(&request(HttpMethod::Post, "/users/42")).is_none());
+        assert!(router.route(&request(HttpMethod::Get, "/posts/42")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_http_server_routes_and_404() {
+        let addr: SocketAddr = "127.0.0.1:8087".parse().unwrap();
+        let mut router = Router::new();
+        router.add_route(
+            HttpMethod::Get,
+            "/users/:id",
+            Arc::new(|_, params| HttpResponse::ok(params["id"].clone())),
+        );
+        let server = HttpServer::new(addr).with_router(router);
+        let server_handle = tokio::spawn(async move { server.run().await.map_err(|e| e.to_string()) });
+        sleep(Duration::from_millis(100)).await;
+
+        for (path, expected) in [("/users/42", "HTTP/1.1 200 OK"), ("/missing", "HTTP/1.1 404 Not Found")] {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(format!("GET {} HTTP/1.1\r\n\r\n", path).as_bytes())
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            assert!(response.starts_with(expected), "{}", response);
+        }
+        server_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_http_server_metrics_endpoint() {
+        let addr: SocketAddr = "127.0.0.1:8093".parse().unwrap();
+        let exporter = Arc::new(PrometheusExporter::new());
+        let requests = exporter.register_counter("server_requests_total", "Запросы").unwrap();
+        requests.inc_by(7);
+        let server = HttpServer::new(addr).with_metrics(Arc::clone(&exporter));
+        let server_handle = tokio::spawn(async move { server.run().await.map_err(|e| e.to_string()) });
+        sleep(Duration::from_millis(100)).await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(b"GET /metrics HTTP/1.1\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.contains("server_requests_total 7"));
+        server_handle.abort();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_http_server_graceful_shutdown() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let addr: SocketAddr = "127.0.0.1:8088".parse().unwrap();
+        let started = Arc::new(AtomicBool::new(false));
+        let handler_started = Arc::clone(&started);
+        let server = HttpServer::new(addr).with_handler(move |_| {
+            // Медленный обработчик
+            handler_started.store(true, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(100));
+            HttpResponse::ok("done")
+        });
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let server_handle = tokio::spawn(async move {
+            server.run_with_shutdown(shutdown_rx).await.map_err(|e| e.to_string())
+        });
+        sleep(Duration::from_millis(50)).await;
+
+        let client = tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(b"GET /slow HTTP/1.1\r\n\r\n").await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        });
+        // Сигнал отправляется, когда обработчик уже выполняется
+        while !started.load(Ordering::SeqCst) {
+            sleep(Duration::from_millis(1)).await;
+        }
+
+        shutdown_tx.send(()).unwrap();
+        let result = timeout(Duration::from_millis(200), server_handle).await;
+        assert!(result.is_ok(), "сервер не завершился за 200 мс");
+        assert_eq!(result.unwrap().unwrap(), Ok(()));
+
+        // Начатый запрос обработан до конца
+        assert!(client.await.unwrap().ends_with("done"));
+        assert!(TcpStream::connect(addr).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_middleware_stack_rejects_missing_auth() {
+        let logging = LoggingMiddleware::new();
+        let stack = Arc::new(
+            MiddlewareStack::new()
+                .with(logging.clone())
+                .with(AuthMiddleware::new("secret")),
+        );
+        let endpoint: RequestHandler = Arc::new(|_| HttpResponse::ok("protected"));
+
+        let response = Arc::clone(&stack)
+            .handle(request(HttpMethod::Get, "/admin"), Arc::clone(&endpoint))
+            .await;
+        assert_eq!(response.status, 401);
+        assert_eq!(response.headers.get("www-authenticate").map(String::as_str), Some("Bearer"));
+
+        let mut authorized = request(HttpMethod::Get, "/admin");
+        authorized
+            .headers
+            .insert("authorization".to_string(), "Bearer secret".to_string());
+        let response = Arc::clone(&stack).handle(authorized, endpoint).await;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, b"protected");
+
+        // Журнал стоит снаружи проверки доступа и видит оба ответа
+        let entries = logging.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].method, entries[0].path.as_str(), entries[0].status), (HttpMethod::Get, "/admin", 401));
+        assert_eq!(entries[1].status, 200);
+    }
+
+    #[tokio::test]
+    async fn test_http_server_with_middleware() {
+        let addr: SocketAddr = "127.0.0.1:8089".parse().unwrap();
+        let server = HttpServer::new(addr)
+            .with_handler(|_| HttpResponse::ok("ok"))
+            .with_middleware(MiddlewareStack::new().with(AuthMiddleware::new("token")));
+        let server_handle = tokio::spawn(async move { server.run().await.map_err(|e| e.to_string()) });
+        sleep(Duration::from_millis(100)).await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 401 Unauthorized"), "{}", response);
+        server_handle.abort();
+    }
+
+    /// Сервер, считающий принятые подключения и удерживающий их открытыми
+    async fn counting_server() -> (SocketAddr, Arc<std::sync::atomic::AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepted = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&accepted);
+        tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                sockets.push(socket);
+            }
+        });
+        (addr, accepted)
+    }
+
+    #[tokio::test]
+    async fn test_connection_pool_waits_and_reuses() {
+        let (addr, accepted) = counting_server().await;
+        let client = WebSocketClient::new(addr);
+        let pool = client.connection_pool(1, Duration::from_secs(60));
+
+        let first = pool.acquire().await.unwrap();
+        let local = first.local_addr().unwrap();
+
+        // Второй запрос ждет, пока первое соединение не вернется в пул
+        let waiting = {
+            let pool = pool.clone();
+            tokio::spawn(async move { pool.acquire().await.unwrap().local_addr().unwrap() })
+        };
+        sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+
+        drop(first);
+        let second = timeout(Duration::from_secs(1), waiting).await.unwrap().unwrap();
+        assert_eq!(second, local);
+        assert_eq!(accepted.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(pool.idle_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_connection_pool_keyed_by_addr() {
+        let (first_addr, first_accepted) = counting_server().await;
+        let (second_addr, second_accepted) = counting_server().await;
+        let pool = ConnectionPool::new(first_addr, 4, Duration::from_secs(60));
+
+        let first = pool.acquire().await.unwrap();
+        let second = pool.acquire_to(second_addr).await.unwrap();
+        let (first_local, second_local) = (first.local_addr().unwrap(), second.local_addr().unwrap());
+        // Heartbeat: ядро отправляет keep-alive пробы по каждому соединению
+        assert!(SockRef::from(&*second).keepalive().unwrap());
+        drop(first);
+        drop(second);
+        assert_eq!(pool.idle_count().await, 2);
+
+        // Соединение возвращается только запросу к своему адресу
+        assert_eq!(pool.acquire_to(second_addr).await.unwrap().local_addr().unwrap(), second_local);
+        assert_eq!(pool.acquire().await.unwrap().local_addr().unwrap(), first_local);
+
+        // Даем серверам обработать accept до подсчета соединений
+        sleep(Duration::from_millis(20)).await;
+        assert_eq!(first_accepted.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(second_accepted.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_connection_pool_keepalive_drops_closed() {
+        // Сервер сразу закрывает принятые соединения
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                drop(socket);
+            }
+        });
+
+        let pool = ConnectionPool::new(addr, 2, Duration::from_millis(20));
+        drop(pool.acquire().await.unwrap());
+        assert_eq!(pool.idle_count().await, 1);
+
+        sleep(Duration::from_millis(80)).await;
+        assert_eq!(pool.idle_count().await, 0);
+    }
+
+    #[test]
+    fn test_websocket_accept_key() {
+        // Пример из RFC 6455, раздел 1.3
+        assert_eq!(websocket_accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+    }
+
+    #[tokio::test]
+    async fn test_websocket_handshake_with_tungstenite() {
+        use futures::StreamExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(socket).await.unwrap();
+            let mut received = Vec::new();
+            for _ in 0..2 {
+                match websocket.next().await {
+                    Some(Ok(Message::Text(text))) => received.push(text),
+                    other => panic!("неожиданное сообщение: {:?}", other),
+                }
+            }
+            received
+        });
+
+        let client = WebSocketClient::new(addr);
+        let mut stream = client.connect().await.unwrap();
+        client.handshake(&mut stream, "/chat").await.unwrap();
+
+        let long = "я".repeat(200);
+        client.send_text_frame(&mut stream, "hello").await.unwrap();
+        client.send_text_frame(&mut stream, &long).await.unwrap();
+
+        assert_eq!(server.await.unwrap(), vec!["hello".to_string(), long]);
+    }
+
+    #[tokio::test]
+    async fn test_websocket_handshake_rejected() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 1024];
+            let _ = socket.read(&mut buffer).await;
+            socket.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await.unwrap();
+        });
+
+        let client = WebSocketClient::new(addr);
+        let mut stream = client.connect().await.unwrap();
+        let result = client.handshake(&mut stream, "/").await;
+        assert!(matches!(result, Err(HandshakeError::UnexpectedStatus(status)) if status == "HTTP/1.1 200 OK"));
+    }
+
+    #[tokio::test]
+    async fn test_udp_multicast() {
+        let group = Ipv4Addr::new(239, 255, 42, 1);
+        let interface = Ipv4Addr::LOCALHOST;
+        let port = 8090;
+
+        let receivers: Vec<UdpServer> = (0..2)
+            .map(|_| {
+                let server = UdpServer::new(SocketAddr::from((Ipv4Addr::UNSPECIFIED, port))).bind().unwrap();
+                server.join_multicast_group(group, interface).unwrap();
+                server
+            })
+            .collect();
+
+        let sender = UdpMulticastSender::new(SocketAddrV4::new(group, port), interface).await.unwrap();
+        sender.send(b"multicast").await.unwrap();
+
+        for receiver in &receivers {
+            let mut buf = [0; 64];
+            let (size, _) = timeout(Duration::from_secs(1), receiver.recv_from(&mut buf))
+                .await
+                .expect("датаграмма не получена")
+                .unwrap();
+            assert_eq!(&buf[..size], b"multicast");
+        }
+
+        // Повторный выход из группы невозможен
+        receivers[0].leave_multicast_group(group, interface).unwrap();
+        assert!(receivers[0].leave_multicast_group(group, interface).is_err());
+    }
+
+    #[test]
+    fn test_udp_multicast_requires_bind() {
+        let server = UdpServer::new("127.0.0.1:0".parse().unwrap());
+        let result = server.join_multicast_group(Ipv4Addr::new(239, 255, 42, 1), Ipv4Addr::LOCALHOST);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotConnected);
+    }
+
+    #[tokio::test]
+    async fn test_https_server() {
+        use rcgen::{BasicConstraints, Certificate, CertificateParams, DnType, IsCa};
+
+        // Тестовый центр сертификации и подписанный им сертификат для localhost
+        let mut ca_params = CertificateParams::new(Vec::new());
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params.distinguished_name.push(DnType::CommonName, "Test CA");
+        let ca = Certificate::from_params(ca_params).unwrap();
+        let leaf = Certificate::from_params(CertificateParams::new(vec!["localhost".to_string()])).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("cert.pem");
+        let key_path = dir.path().join("key.pem");
+        std::fs::write(&cert_path, leaf.serialize_pem_with_signer(&ca).unwrap()).unwrap();
+        std::fs::write(&key_path, leaf.serialize_private_key_pem()).unwrap();
+
+        let addr: SocketAddr = "127.0.0.1:8091".parse().unwrap();
+        let server = HttpServer::new_tls(addr, &cert_path, &key_path)
+            .unwrap()
+            .with_handler(|request| HttpResponse::ok(format!("secure {}", request.path)));
+        let server_handle = tokio::spawn(async move { server.run().await.map_err(|e| e.to_string()) });
+        sleep(Duration::from_millis(100)).await;
+
+        let ca_cert = reqwest::Certificate::from_pem(ca.serialize_pem().unwrap().as_bytes()).unwrap();
+        let client = reqwest::Client::builder().add_root_certificate(ca_cert).build().unwrap();
+        let response = client.get("https://localhost:8091/hello").send().await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.text().await.unwrap(), "secure /hello");
+        server_handle.abort();
+    }
+
+    #[test]
+    fn test_new_tls_missing_files() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let missing = Path::new("/nonexistent/cert.pem");
+        assert!(HttpServer::new_tls(addr, missing, missing).is_err());
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let response = HttpResponse::parse(b"HTTP/1.1 404 Not Found\r\nContent-Length: 4\r\n\r\nnope").unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, b"nope");
+
+        // Без Content-Length тело читается до конца данных
+        let response = HttpResponse::parse(b"HTTP/1.0 200 OK\r\n\r\nall the rest").unwrap();
+        assert_eq!(response.body, b"all the rest");
+
+        let response = HttpResponse::ok("roundtrip").with_header("X-Test", "1");
+        assert_eq!(HttpResponse::parse(&response.to_bytes()).unwrap().body, response.body);
+
+        assert!(matches!(HttpResponse::parse(b"HTTP/1.1 abc\r\n\r\n"), Err(ParseError::InvalidStatusLine(_))));
+    }
+
+    #[test]
+    fn test_parse_url() {
+        assert_eq!(parse_url("http://localhost:8080/a/b?c=1").unwrap(), ("localhost", 8080, "/a/b?c=1"));
+        assert_eq!(parse_url("http://example.com").unwrap(), ("example.com", 80, "/"));
+        assert!(matches!(parse_url("https://example.com"), Err(ClientError::UnsupportedScheme(_))));
+        assert!(matches!(parse_url("localhost:8080"), Err(ClientError::InvalidUrl(_))));
+        assert!(matches!(parse_url("http://host:port/"), Err(ClientError::InvalidUrl(_))));
+    }
+
+    #[tokio::test]
+    async fn test_http_client_roundtrip() {
+        let addr: SocketAddr = "127.0.0.1:8092".parse().unwrap();
+        let server = HttpServer::new(addr).with_handler(|request| {
+            let mut body = format!("{} {} ", request.method, request.path).into_bytes();
+            body.extend_from_slice(&request.body);
+            HttpResponse::ok(body)
+        });
+        let server_handle = tokio::spawn(async move { server.run().await.map_err(|e| e.to_string()) });
+        sleep(Duration::from_millis(100)).await;
+
+        let client = HttpClient::new().with_timeout(Duration::from_secs(5));
+        let response = client.get("http://127.0.0.1:8092/items").await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, b"GET /items ");
+
+        let response = client.post("http://127.0.0.1:8092/items", br#"{"id":1}"#).await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, br#"POST /items {"id":1}"#);
+        server_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_http_client_timeout() {
+        // Сервер принимает соединение, но не отвечает
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _socket = listener.accept().await;
+            sleep(Duration::from_secs(5)).await;
+        });
+
+        let client = HttpClient::new().with_timeout(Duration::from_millis(100));
+        let result = client.get(&format!("http://{}/", addr)).await;
+        assert!(matches!(result, Err(ClientError::Timeout(_))));
+    }
+
+    /// Запрос ровно из 512 байт с 10 заголовками
+    fn sample_raw_request() -> Vec<u8> {
+        let mut request = b"POST /api/items?page=2 HTTP/1.1\r\n".to_vec();
+        for i in 0..8 {
+            request.extend_from_slice(format!("X-Header-{}: value-{}\r\n", i, i).as_bytes());
+        }
+        request.extend_from_slice(b"Content-Length: 4\r\n");
+        let padding = 512 - request.len() - "X-Padding: \r\n\r\nbody".len();
+        request.extend_from_slice(format!("X-Padding: {}\r\n\r\nbody", "p".repeat(padding)).as_bytes());
+        request
+    }
+
+    #[test]
+    fn test_parse_raw() {
+        let buffer = sample_raw_request();
+        assert_eq!(buffer.len(), 512);
+
+        let request = parse_raw(&buffer).unwrap();
+        assert_eq!(request.method, b"POST");
+        assert_eq!(request.path, b"/api/items?page=2");
+        assert_eq!(request.version, b"HTTP/1.1");
+        assert_eq!(request.headers.len(), 10);
+        assert_eq!(request.headers[3], (&b"X-Header-3"[..], &b"value-3"[..]));
+        assert_eq!(request.header("content-length"), Some(&b"4"[..]));
+        assert_eq!(request.body, b"body");
+
+        // Поля указывают внутрь исходного буфера
+        let range = buffer.as_ptr_range();
+        assert!(range.contains(&request.path.as_ptr()));
+
+        // Результат совпадает с копирующим разбором
+        let owned = HttpRequest::parse(&buffer).unwrap();
+        assert_eq!(owned.path.as_bytes(), request.path);
+        assert_eq!(owned.body, request.body);
+
+        assert_eq!(parse_raw(&buffer[..100]), Err(ParseError::Incomplete));
+        assert_eq!(parse_raw(&buffer[..buffer.len() - 1]), Err(ParseError::Incomplete));
+        assert!(matches!(
+            parse_raw(b"GET /\r\n\r\n"),
+            Err(ParseError::InvalidRequestLine(_))
+        ));
+    }
 } 
\ No newline at end of file
@@ -11,6 +11,8 @@
 //! - Оптимизация структур данных
 //! - Оптимизация сетевого кода
 //! - Оптимизация работы с базой данных
+//! - SIMD векторизация
+//! - Интернирование строк
 
 use std::time::Instant;
 use std::collections::{HashMap, VecDeque};
@@ -106,6 +108,136 @@ fn process_batch(&self, batch: &[u8]) {
     }
 }
 
+/// Дешевая копируемая ссылка на интернированную строку
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InternedString(u32);
+
+/// Интернирование строк: каждая уникальная строка хранится один раз
+///
+/// Таблица поиска и хранилище разделяют одну аллокацию `Arc<str>`.
+#[derive(Debug, Default)]
+pub struct StringInterner {
+    strings: Vec<Arc<str>>,
+    lookup: HashMap<Arc<str>, InternedString>,
+}
+
+impl StringInterner {
+    /// Создание пустого интернера
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Получение ссылки на строку; повторный вызов не выделяет память
+    pub fn intern(&mut self, s: &str) -> InternedString {
+        if let Some(&handle) = self.lookup.get(s) {
+            return handle;
+        }
+        let handle = InternedString(
+            u32::try_from(self.strings.len()).expect("слишком много интернированных строк"),
+        );
+        let stored: Arc<str> = Arc::from(s);
+        self.strings.push(Arc::clone(&stored));
+        self.lookup.insert(stored, handle);
+        handle
+    }
+
+    /// Строка по ссылке; ссылка другого интернера приводит к панике
+    pub fn resolve(&self, handle: InternedString) -> &str {
+        &self.strings[handle.0 as usize]
+    }
+
+    /// Количество уникальных строк
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Пуст ли интернер
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+/// Потокобезопасный интернер, общий для всех клонов
+#[derive(Debug, Clone, Default)]
+pub struct GlobalInterner {
+    inner: Arc<Mutex<StringInterner>>,
+}
+
+impl GlobalInterner {
+    /// Создание пустого интернера
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Интернирование строки
+    pub fn intern(&self, s: &str) -> InternedString {
+        self.inner.lock().intern(s)
+    }
+
+    /// Строка по ссылке, без копирования содержимого
+    pub fn resolve(&self, handle: InternedString) -> Arc<str> {
+        Arc::clone(&self.inner.lock().strings[handle.0 as usize])
+    }
+
+    /// Количество уникальных строк
+    pub fn len(&self) -> usize {
+        self.inner.lock().len()
+    }
+
+    /// Пуст ли интернер
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().is_empty()
+    }
+}
+
+/// Скалярное произведение, эталон для SIMD версии
+pub fn dot_product_scalar(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len(), "векторы разной длины");
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Скалярное произведение по 4 числа за инструкцию (SSE4.1 `dpps`)
+///
+/// Наличие SSE4.1 проверяется во время выполнения, на других
+/// процессорах и архитектурах используется скалярная версия.
+pub fn dot_product_simd(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len(), "векторы разной длины");
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("sse4.1") {
+            // SAFETY: поддержка SSE4.1 только что проверена
+            return unsafe { dot_product_sse41(a, b) };
+        }
+    }
+
+    dot_product_scalar(a, b)
+}
+
+/// # Safety
+///
+/// Процессор должен поддерживать SSE4.1, а срезы иметь одинаковую длину.
+/// Невыровненные загрузки читают ровно 4 числа из `chunks_exact(4)`,
+/// поэтому выхода за границы срезов нет.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse4.1")]
+unsafe fn dot_product_sse41(a: &[f32], b: &[f32]) -> f32 {
+    use std::arch::x86_64::{_mm_add_ps, _mm_cvtss_f32, _mm_dp_ps, _mm_loadu_ps, _mm_setzero_ps};
+
+    let a_chunks = a.chunks_exact(4);
+    let b_chunks = b.chunks_exact(4);
+    let tail = dot_product_scalar(a_chunks.remainder(), b_chunks.remainder());
+
+    let mut acc = _mm_setzero_ps();
+    for (x, y) in a_chunks.zip(b_chunks) {
+        let vx = _mm_loadu_ps(x.as_ptr());
+        let vy = _mm_loadu_ps(y.as_ptr());
+        // 0xF1: перемножить все 4 полосы и положить сумму в нулевую
+        acc = _mm_add_ps(acc, _mm_dp_ps::<0xF1>(vx, vy));
+    }
+    _mm_cvtss_f32(acc) + tail
+}
+
 /// Демонстрация оптимизации кода
 pub fn demonstrate_optimization() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n=== Демонстрация оптимизации кода ===");
@@ -140,6 +272,25 @@ pub fn demonstrate_optimization() -> Result<(), Box<dyn std::error::Error>> {
     }
     println!("Оптимизированная хеш-карта создана");
 
+    // Демонстрация SIMD
+    println!("\n5. SIMD:");
+    let a: Vec<f32> = (0..1000).map(|i| i as f32 * 0.5).collect();
+    let b: Vec<f32> = (0..1000).map(|i| (1000 - i) as f32).collect();
+    println!(
+        "Скалярное произведение: {} (SIMD), {} (скалярно)",
+        dot_product_simd(&a, &b),
+        dot_product_scalar(&a, &b)
+    );
+
+    // Демонстрация интернирования строк
+    println!("\n6. Интернирование строк:");
+    let mut interner = StringInterner::new();
+    let handles: Vec<InternedString> = ["GET", "POST", "GET", "GET"]
+        .iter()
+        .map(|method| interner.intern(method))
+        .collect();
+    println!("Ссылки: {:?}, уникальных строк: {}", handles, interner.len());
+
     Ok(())
 }
 
@@ -164,4 +315,52 @@ fn test_performance_optimization() {
         opt.process_data(&data);
         assert_eq!(opt.buffer.len(), data.len());
     }
+
+    #[test]
+    fn test_dot_product_simd() {
+        assert_eq!(dot_product_simd(&[], &[]), 0.0);
+        assert_eq!(dot_product_simd(&[1.0, 2.0, 3.0, 4.0], &[5.0, 6.0, 7.0, 8.0]), 70.0);
+
+        // Длины, не кратные 4, проверяют обработку хвоста
+        for len in [1, 3, 5, 8, 13, 100] {
+            let a: Vec<f32> = (0..len).map(|i| i as f32 * 0.25 - 3.0).collect();
+            let b: Vec<f32> = (0..len).map(|i| 2.0 - i as f32 * 0.5).collect();
+            let expected = dot_product_scalar(&a, &b);
+            let actual = dot_product_simd(&a, &b);
+            assert!((expected - actual).abs() <= expected.abs() * 1e-5 + 1e-4, "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_string_interner() {
+        let mut interner = StringInterner::new();
+        let first = interner.intern("повтор");
+        for _ in 0..1000 {
+            assert_eq!(interner.intern("повтор"), first);
+        }
+        assert_eq!(interner.len(), 1);
+        // Хранилище и таблица поиска ссылаются на одну строку
+        assert_eq!(Arc::strong_count(&interner.strings[0]), 2);
+
+        let other = interner.intern("другая");
+        assert_ne!(other, first);
+        assert_eq!(interner.resolve(first), "повтор");
+        assert_eq!(interner.resolve(other), "другая");
+    }
+
+    #[test]
+    fn test_global_interner() {
+        let interner = GlobalInterner::new();
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let interner = interner.clone();
+                std::thread::spawn(move || interner.intern("общая"))
+            })
+            .collect();
+        let handles: Vec<InternedString> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+
+        assert!(handles.iter().all(|&handle| handle == handles[0]));
+        assert_eq!(interner.len(), 1);
+        assert_eq!(&*interner.resolve(handles[0]), "общая");
+    }
 } 
\ No newline at end of file
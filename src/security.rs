@@ -11,10 +11,22 @@
 //! - Безопасное логирование
 //! - Защита от утечек памяти
 //! - Безопасное многопоточное программирование
+//! - Сравнение за постоянное время
+//! - Аутентифицированное шифрование AES-256-GCM
+//! - Подпись запросов HMAC-SHA256
+//! - JWT с алгоритмом HS256
+//! - Проверка стойкости паролей
 
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL;
+use base64::Engine as _;
 use parking_lot::Mutex;
-use ring::{rand, pbkdf2, digest};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ring::{rand, pbkdf2, digest, hmac};
 use ring::rand::SecureRandom;
 use ring::pbkdf2::{PBKDF2_HMAC_SHA256, derive};
 use ring::digest::{SHA256, SHA512};
@@ -26,11 +38,240 @@ pub struct CryptoDemo {
     salt: Vec<u8>,
 }
 
+/// Размер nonce AES-GCM
+const NONCE_SIZE: usize = 12;
+
+/// Размер тега аутентификации AES-GCM
+const TAG_SIZE: usize = 16;
+
+/// Ошибка шифрования или расшифровки
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CryptoError {
+    #[error("Шифротекст из {0} байт короче nonce и тега")]
+    TooShort(usize),
+
+    #[error("Ошибка шифрования")]
+    Encryption,
+
+    #[error("Расшифровка не удалась: неверный ключ или данные изменены")]
+    Decryption,
+}
+
+/// Утверждения JWT: субъект, время истечения и выпуска (секунды Unix)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JwtClaims {
+    pub sub: String,
+    pub exp: u64,
+    pub iat: u64,
+}
+
+/// Заголовок JWT
+#[derive(Debug, Serialize, Deserialize)]
+struct JwtHeader {
+    alg: String,
+    typ: String,
+}
+
+/// Ошибка проверки JWT
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum JwtError {
+    #[error("Токен не состоит из трех частей base64url")]
+    Malformed,
+
+    #[error("Неподдерживаемый алгоритм: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("Подпись токена неверна")]
+    InvalidSignature,
+
+    #[error("Срок действия токена истек")]
+    Expired,
+}
+
+/// Пароль не прошел проверку стойкости
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PasswordError {
+    #[error("Слабый пароль: {}", .0.join("; "))]
+    TooWeak(Vec<String>),
+}
+
+/// Минимальная длина пароля в символах
+pub const MIN_PASSWORD_LENGTH: usize = 12;
+
+/// Максимальное число одинаковых символов подряд
+const MAX_REPEATED_CHARS: usize = 3;
+
+/// Распространенные пароли и их фрагменты (сравнение без учета регистра)
+const COMMON_PASSWORDS: &[&str] = &[
+    "password", "123456", "qwerty", "letmein", "welcome", "iloveyou", "admin", "monkey",
+    "dragon", "111111",
+];
+
 /// Структура для демонстрации безопасного хранения данных
+///
+/// Каждая запись хранится зашифрованной AES-256-GCM со своим nonce.
 #[derive(Debug)]
 pub struct SecureStorage {
-    data: Arc<Mutex<Vec<u8>>>,
-    key: Vec<u8>,
+    data: Arc<Mutex<Vec<Vec<u8>>>>,
+    key: [u8; 32],
+}
+
+/// Сравнение байтов за время, не зависящее от позиции первого различия
+///
+/// Обычное `==` выходит на первом несовпавшем байте, и по времени ответа
+/// можно подбирать секрет (MAC, токен) побайтно. Здесь всегда проходится
+/// вся длинная последовательность, а различия копятся через XOR. Длина
+/// при этом не скрывается.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let (difference, _) = accumulate_difference(a, b);
+    difference == 0
+}
+
+/// XOR всех пар байтов и число просмотренных позиций
+fn accumulate_difference(a: &[u8], b: &[u8]) -> (u8, usize) {
+    let len = a.len().max(b.len());
+    // Разная длина — уже различие, но проход все равно полный
+    let mut difference = u8::from(a.len() != b.len());
+    let mut examined = 0;
+    for i in 0..len {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        difference |= x ^ y;
+        examined += 1;
+    }
+    // black_box не дает оптимизатору превратить цикл в ранний выход
+    (std::hint::black_box(difference), examined)
+}
+
+/// Проверка HMAC-SHA256 с постоянным временем сравнения
+pub fn constant_time_hmac_verify(key: &[u8], data: &[u8], expected_mac: &[u8]) -> bool {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+    let mac = hmac::sign(&key, data);
+    constant_time_eq(mac.as_ref(), expected_mac)
+}
+
+/// Шифрование AES-256-GCM; результат — nonce (12 байт), шифротекст и тег
+///
+/// Nonce генерируется случайно для каждого сообщения: повтор nonce с тем
+/// же ключом раскрывает XOR открытых текстов и позволяет подделывать теги.
+pub fn encrypt(key: &[u8; 32], plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let cipher = Aes256Gcm::new(key.into());
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher.encrypt(&nonce, plaintext).map_err(|_| CryptoError::Encryption)?;
+
+    let mut output = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+    output.extend_from_slice(&nonce);
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+/// Расшифровка результата `encrypt` с проверкой тега
+pub fn decrypt(key: &[u8; 32], ciphertext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if ciphertext.len() < NONCE_SIZE + TAG_SIZE {
+        return Err(CryptoError::TooShort(ciphertext.len()));
+    }
+    let (nonce, ciphertext) = ciphertext.split_at(NONCE_SIZE);
+    Aes256Gcm::new(key.into())
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptoError::Decryption)
+}
+
+/// Создание JWT с подписью HMAC-SHA256 (HS256)
+pub fn create_jwt(claims: &JwtClaims, secret: &[u8]) -> String {
+    let header = JwtHeader {
+        alg: "HS256".to_string(),
+        typ: "JWT".to_string(),
+    };
+    let header = serde_json::to_vec(&header).expect("заголовок сериализуется в JSON");
+    let payload = serde_json::to_vec(claims).expect("утверждения сериализуются в JSON");
+    let signing_input = format!("{}.{}", BASE64_URL.encode(header), BASE64_URL.encode(payload));
+
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    let signature = hmac::sign(&key, signing_input.as_bytes());
+    format!("{}.{}", signing_input, BASE64_URL.encode(signature))
+}
+
+/// Проверка подписи и срока действия JWT
+///
+/// Алгоритм из заголовка должен быть HS256: иначе подделанный токен с
+/// `"alg": "none"` прошел бы без подписи.
+pub fn validate_jwt(token: &str, secret: &[u8]) -> Result<JwtClaims, JwtError> {
+    let mut parts = token.split('.');
+    let (header, payload, signature) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(header), Some(payload), Some(signature), None) => (header, payload, signature),
+        _ => return Err(JwtError::Malformed),
+    };
+
+    let decoded_header: JwtHeader = decode_json_part(header)?;
+    if decoded_header.alg != "HS256" {
+        return Err(JwtError::UnsupportedAlgorithm(decoded_header.alg));
+    }
+
+    let signature = BASE64_URL.decode(signature).map_err(|_| JwtError::Malformed)?;
+    let signing_input = &token[..header.len() + 1 + payload.len()];
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    hmac::verify(&key, signing_input.as_bytes(), &signature).map_err(|_| JwtError::InvalidSignature)?;
+
+    let claims: JwtClaims = decode_json_part(payload)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("системное время после 1970 года")
+        .as_secs();
+    if claims.exp <= now {
+        return Err(JwtError::Expired);
+    }
+    Ok(claims)
+}
+
+/// Разбор части JWT: base64url, затем JSON
+fn decode_json_part<T: serde::de::DeserializeOwned>(part: &str) -> Result<T, JwtError> {
+    let bytes = BASE64_URL.decode(part).map_err(|_| JwtError::Malformed)?;
+    serde_json::from_slice(&bytes).map_err(|_| JwtError::Malformed)
+}
+
+/// Проверка стойкости пароля перед выводом ключа
+///
+/// Возвращает все нарушенные правила сразу, а не только первое.
+pub fn validate_password_strength(password: &str) -> Result<(), PasswordError> {
+    let mut failures = Vec::new();
+
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        failures.push(format!("длина меньше {} символов", MIN_PASSWORD_LENGTH));
+    }
+    if !password.chars().any(char::is_uppercase) {
+        failures.push("нет заглавной буквы".to_string());
+    }
+    if !password.chars().any(char::is_lowercase) {
+        failures.push("нет строчной буквы".to_string());
+    }
+    if !password.chars().any(|c| c.is_ascii_digit()) {
+        failures.push("нет цифры".to_string());
+    }
+    if password.chars().all(char::is_alphanumeric) {
+        failures.push("нет специального символа".to_string());
+    }
+
+    let lowercase = password.to_lowercase();
+    if let Some(pattern) = COMMON_PASSWORDS.iter().find(|p| lowercase.contains(*p)) {
+        failures.push(format!("содержит распространенный шаблон \"{}\"", pattern));
+    }
+
+    let mut run = 0;
+    let mut previous = None;
+    for c in password.chars() {
+        run = if previous == Some(c) { run + 1 } else { 1 };
+        previous = Some(c);
+        if run > MAX_REPEATED_CHARS {
+            failures.push(format!("символ '{}' повторяется более {} раз подряд", c, MAX_REPEATED_CHARS));
+            break;
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(PasswordError::TooWeak(failures))
+    }
 }
 
 impl CryptoDemo {
@@ -60,39 +301,46 @@ pub fn hash_data(&self, data: &[u8]) -> Vec<u8> {
         let digest = digest::digest(&SHA256, data);
         digest.as_ref().to_vec()
     }
+
+    /// Подпись HMAC-SHA256
+    ///
+    /// В отличие от `hash_data`, подпись без ключа не подделать: хеш
+    /// измененного запроса может пересчитать любой, а HMAC — только
+    /// владелец ключа.
+    pub fn sign_hmac(&self, key: &[u8], data: &[u8]) -> Vec<u8> {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+        hmac::sign(&key, data).as_ref().to_vec()
+    }
+
+    /// Проверка подписи HMAC-SHA256 за постоянное время
+    pub fn verify_hmac(&self, key: &[u8], data: &[u8], signature: &[u8]) -> bool {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+        hmac::verify(&key, data, signature).is_ok()
+    }
 }
 
 impl SecureStorage {
-    /// Создание нового экземпляра
-    pub fn new(key: Vec<u8>) -> Self {
+    /// Создание хранилища с 256-битным ключом
+    pub fn new(key: [u8; 32]) -> Self {
         Self {
             data: Arc::new(Mutex::new(Vec::new())),
             key,
         }
     }
 
-    /// Безопасное хранение данных
+    /// Шифрование и сохранение записи
     pub fn store_data(&self, data: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
-        let mut storage = self.data.lock();
-        // Шифрование данных перед хранением
-        let mut encrypted = vec![0u8; data.len()];
-        for (i, &byte) in data.iter().enumerate() {
-            encrypted[i] = byte ^ self.key[i % self.key.len()];
-        }
-        storage.extend_from_slice(&encrypted);
+        let encrypted = encrypt(&self.key, data)?;
+        self.data.lock().push(encrypted);
         Ok(())
     }
 
-    /// Безопасное получение данных
+    /// Расшифровка записи с номером `index`
+    ///
+    /// Возвращает `None`, если записи нет или ее тег не прошел проверку.
     pub fn retrieve_data(&self, index: usize) -> Option<Vec<u8>> {
         let storage = self.data.lock();
-        if index >= storage.len() {
-            return None;
-        }
-        // Дешифрование данных
-        let mut decrypted = vec![0u8; 1];
-        decrypted[0] = storage[index] ^ self.key[index % self.key.len()];
-        Some(decrypted)
+        decrypt(&self.key, storage.get(index)?).ok()
     }
 }
 
@@ -111,15 +359,45 @@ pub fn demonstrate_security() -> Result<(), Box<dyn std::error::Error>> {
     let data = b"Hello, World!";
     let hash = crypto.hash_data(data);
     println!("Хеш данных: {:?}", hash);
+    let signature = crypto.sign_hmac(b"api-key", data);
+    println!("Подпись HMAC верна: {}", crypto.verify_hmac(b"api-key", data, &signature));
 
     // Демонстрация безопасного хранения
     println!("\n2. Безопасное хранение:");
+    let key: [u8; 32] = key.try_into().map_err(|_| "ключ должен быть 32 байта")?;
     let storage = SecureStorage::new(key);
     storage.store_data(data)?;
     if let Some(retrieved) = storage.retrieve_data(0) {
         println!("Получены данные: {:?}", retrieved);
     }
 
+    // Демонстрация сравнения за постоянное время
+    println!("\n3. Сравнение за постоянное время:");
+    let mac = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, b"secret"), data);
+    println!("MAC верен: {}", constant_time_hmac_verify(b"secret", data, mac.as_ref()));
+    println!("Чужой MAC верен: {}", constant_time_hmac_verify(b"other", data, mac.as_ref()));
+
+    // Демонстрация JWT
+    println!("\n4. JWT:");
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let claims = JwtClaims {
+        sub: "alice".to_string(),
+        exp: now + 3600,
+        iat: now,
+    };
+    let token = create_jwt(&claims, b"jwt-secret");
+    println!("Токен: {}", token);
+    println!("Проверка: {:?}", validate_jwt(&token, b"jwt-secret"));
+
+    // Демонстрация проверки паролей
+    println!("\n5. Стойкость паролей:");
+    for password in ["password", "P@ssw0rdABC123"] {
+        match validate_password_strength(password) {
+            Ok(()) => println!("{}: стойкий", password),
+            Err(e) => println!("{}: {}", password, e),
+        }
+    }
+
     Ok(())
 }
 
@@ -135,12 +413,151 @@ fn test_crypto() {
         assert_eq!(key.len(), 32);
     }
 
+    #[test]
+    fn test_hmac_sign_and_verify() {
+        let crypto = CryptoDemo::new();
+        let key = b"request signing key";
+        let mut message = b"GET /api/users?page=2".to_vec();
+
+        let signature = crypto.sign_hmac(key, &message);
+        assert_eq!(signature.len(), 32);
+        assert!(crypto.verify_hmac(key, &message, &signature));
+        assert!(!crypto.verify_hmac(b"wrong key", &message, &signature));
+
+        // Подделка: изменен один байт запроса
+        message[18] = b'9';
+        assert!(!crypto.verify_hmac(key, &message, &signature));
+    }
+
     #[test]
     fn test_secure_storage() {
-        let key = vec![1, 2, 3, 4];
+        let key = [7u8; 32];
         let storage = SecureStorage::new(key);
         let data = b"test";
         storage.store_data(data).unwrap();
         assert!(storage.retrieve_data(0).is_some());
+        assert_eq!(storage.retrieve_data(0).unwrap(), data);
+        assert_eq!(storage.retrieve_data(1), None);
+
+        // В памяти лежит шифротекст, а не открытые данные
+        let stored = storage.data.lock()[0].clone();
+        assert_eq!(stored.len(), NONCE_SIZE + data.len() + TAG_SIZE);
+        assert!(!stored.windows(data.len()).any(|w| w == data));
+    }
+
+    #[test]
+    fn test_aes_gcm_round_trip_and_tampering() {
+        let key = [42u8; 32];
+        let message: Vec<u8> = (0..64).collect();
+
+        let ciphertext = encrypt(&key, &message).unwrap();
+        assert_eq!(decrypt(&key, &ciphertext).unwrap(), message);
+        // Случайный nonce: одинаковые сообщения шифруются по-разному
+        assert_ne!(encrypt(&key, &message).unwrap(), ciphertext);
+
+        let mut tampered = ciphertext.clone();
+        tampered[NONCE_SIZE + 10] ^= 0x01;
+        assert_eq!(decrypt(&key, &tampered), Err(CryptoError::Decryption));
+        assert_eq!(decrypt(&[0u8; 32], &ciphertext), Err(CryptoError::Decryption));
+        assert_eq!(decrypt(&key, &ciphertext[..20]), Err(CryptoError::TooShort(20)));
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(constant_time_eq(b"", b""));
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        // Совпадающий префикс при разной длине — не равенство
+        assert!(!constant_time_eq(b"token", b"token\0"));
+        assert!(!constant_time_eq(b"", b"\0"));
+    }
+
+    #[test]
+    fn test_constant_time_eq_does_not_short_circuit() {
+        let a = [0u8; 64];
+        let mut b = [0u8; 64];
+        b[0] = 1;
+        // Различие в первом байте, но просмотрены все позиции
+        assert_eq!(accumulate_difference(&a, &b), (1, 64));
+        assert_eq!(accumulate_difference(&a[..10], &b), (1, 64));
+    }
+
+    #[test]
+    fn test_constant_time_hmac_verify() {
+        let key = b"shared secret";
+        let mac = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), b"payload");
+        assert!(constant_time_hmac_verify(key, b"payload", mac.as_ref()));
+        assert!(!constant_time_hmac_verify(key, b"payload!", mac.as_ref()));
+        assert!(!constant_time_hmac_verify(key, b"payload", &mac.as_ref()[..16]));
+    }
+
+    fn unix_now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    #[test]
+    fn test_jwt_create_and_validate() {
+        let secret = b"jwt secret";
+        let claims = JwtClaims {
+            sub: "user-42".to_string(),
+            exp: unix_now() + 600,
+            iat: unix_now(),
+        };
+        let token = create_jwt(&claims, secret);
+        assert_eq!(token.split('.').count(), 3);
+        assert!(!token.contains('='));
+        assert_eq!(validate_jwt(&token, secret), Ok(claims.clone()));
+
+        assert_eq!(validate_jwt(&token, b"other secret"), Err(JwtError::InvalidSignature));
+        assert_eq!(validate_jwt("abc.def", secret), Err(JwtError::Malformed));
+
+        // Подмена утверждений ломает подпись
+        let forged_payload = BASE64_URL.encode(
+            serde_json::to_vec(&JwtClaims { sub: "admin".to_string(), ..claims }).unwrap(),
+        );
+        let parts: Vec<&str> = token.split('.').collect();
+        let forged = format!("{}.{}.{}", parts[0], forged_payload, parts[2]);
+        assert_eq!(validate_jwt(&forged, secret), Err(JwtError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_jwt_expired_and_alg_none() {
+        let secret = b"jwt secret";
+        let expired = JwtClaims {
+            sub: "user-42".to_string(),
+            exp: unix_now() - 10,
+            iat: unix_now() - 3600,
+        };
+        assert_eq!(validate_jwt(&create_jwt(&expired, secret), secret), Err(JwtError::Expired));
+
+        let header = BASE64_URL.encode(br#"{"alg":"none","typ":"JWT"}"#);
+        let payload = BASE64_URL.encode(br#"{"sub":"admin","exp":99999999999,"iat":0}"#);
+        assert_eq!(
+            validate_jwt(&format!("{}.{}.", header, payload), secret),
+            Err(JwtError::UnsupportedAlgorithm("none".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_password_strength() {
+        assert_eq!(validate_password_strength("P@ssw0rdABC123"), Ok(()));
+
+        let PasswordError::TooWeak(failures) = validate_password_strength("password").unwrap_err();
+        assert_eq!(
+            failures,
+            vec![
+                "длина меньше 12 символов",
+                "нет заглавной буквы",
+                "нет цифры",
+                "нет специального символа",
+                "содержит распространенный шаблон \"password\"",
+            ]
+        );
+
+        let PasswordError::TooWeak(failures) = validate_password_strength("Xk#9mAAAAq2!").unwrap_err();
+        assert_eq!(failures, vec!["символ 'A' повторяется более 3 раз подряд"]);
+
+        // Три одинаковых символа подряд еще допустимы
+        assert_eq!(validate_password_strength("Xk#9mAAAq2!z"), Ok(()));
     }
 } 
\ No newline at end of file
@@ -6,9 +6,18 @@
 //! - Асинхронные тесты
 //! - Тесты с моками
 //! - Тесты производительности
-
-use std::time::Duration;
+//! - Тестирование свойств (proptest)
+//! - Управляемые часы для тестов, зависящих от времени
+//! - Табличные тесты алгоритмов поиска и сортировки
+//! - Шпион, записывающий вызовы `DataProvider`
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use tokio::sync::Notify;
 use tokio::time::sleep;
+use tracing::Instrument;
 use mockall::predicate::*;
 use mockall::automock;
 
@@ -19,6 +28,157 @@ pub trait DataProvider {
     fn process_data(&self, data: &str) -> Result<String, String>;
 }
 
+/// Буфер, в который пишет fmt-подписчик в тестах
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub(crate) struct CapturedOutput(Arc<Mutex<Vec<u8>>>);
+
+#[cfg(test)]
+impl CapturedOutput {
+    /// Записанный текст
+    pub(crate) fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+#[cfg(test)]
+impl std::io::Write for CapturedOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Шпион: делегирует вызовы `inner` и записывает каждый вызов в `calls`
+///
+/// В отличие от `MockDataProvider` не задает поведение, а только фиксирует
+/// порядок вызовов настоящего провайдера.
+pub struct SpyDataProvider {
+    inner: Box<dyn DataProvider>,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl SpyDataProvider {
+    /// Обертка над провайдером с пустым журналом вызовов
+    pub fn new(inner: Box<dyn DataProvider>) -> Self {
+        Self {
+            inner,
+            calls: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Общий журнал вызовов; остается доступен после передачи шпиона владельцу
+    pub fn calls(&self) -> Arc<Mutex<Vec<String>>> {
+        self.calls.clone()
+    }
+
+    fn record(&self, call: String) {
+        self.calls.lock().unwrap().push(call);
+    }
+}
+
+impl DataProvider for SpyDataProvider {
+    fn get_data(&self) -> Vec<String> {
+        self.record("get_data()".to_string());
+        self.inner.get_data()
+    }
+
+    fn process_data(&self, data: &str) -> Result<String, String> {
+        self.record(format!("process_data({:?})", data));
+        self.inner.process_data(data)
+    }
+}
+
+/// Источник времени; в тестах заменяется на `MockClock`
+#[async_trait]
+pub trait Clock: Send + Sync {
+    /// Текущий момент
+    fn now(&self) -> Instant;
+
+    /// Ожидание в течение `duration`
+    async fn sleep(&self, duration: Duration);
+}
+
+impl fmt::Debug for dyn Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Clock")
+    }
+}
+
+/// Системные часы на основе `tokio::time`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealClock;
+
+#[async_trait]
+impl Clock for RealClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        sleep(duration).await;
+    }
+}
+
+/// Управляемые часы: время идет только при вызове `advance`
+#[derive(Debug)]
+pub struct MockClock {
+    start: Instant,
+    elapsed: Mutex<Duration>,
+    advanced: Notify,
+}
+
+impl MockClock {
+    /// Создание часов, остановленных в текущем моменте
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+            advanced: Notify::new(),
+        }
+    }
+
+    /// Сдвиг времени вперед; пробуждает ожидания, срок которых наступил
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+        self.advanced.notify_waiters();
+    }
+
+    /// Время, прошедшее с создания часов
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        let deadline = self.now() + duration;
+        loop {
+            // Подписка до проверки, чтобы не пропустить advance между ними
+            let advanced = self.advanced.notified();
+            if self.now() >= deadline {
+                return;
+            }
+            advanced.await;
+        }
+    }
+}
+
 /// Структура для демонстрации тестирования
 #[derive(Debug)]
 pub struct TestDemo {
@@ -29,6 +189,7 @@ pub struct TestDemo {
 #[derive(Debug)]
 pub struct AsyncTestDemo {
     provider: Box<dyn DataProvider>,
+    clock: Arc<dyn Clock>,
 }
 
 impl TestDemo {
@@ -64,19 +225,38 @@ pub fn combine_data(&self, other: &[String]) -> Vec<String> {
 impl AsyncTestDemo {
     /// Создание нового экземпляра
     pub fn new(provider: Box<dyn DataProvider>) -> Self {
-        Self { provider }
+        Self {
+            provider,
+            clock: Arc::new(RealClock),
+        }
+    }
+
+    /// Замена системных часов, например на `MockClock` в тестах
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
     }
 
     /// Асинхронная обработка данных
+    ///
+    /// Guard из `Span::enter` нельзя держать через `.await`: после
+    /// пробуждения задача может продолжиться в другом потоке, и спан
+    /// окажется активным не там. Поэтому синхронные участки выполняются
+    /// через `in_scope`, а ожидания привязываются к спану через `instrument`.
     pub async fn process_data(&self) -> Result<Vec<String>, String> {
-        let data = self.provider.get_data();
+        let span = tracing::info_span!("process_data");
+        let data = span.in_scope(|| self.provider.get_data());
         let mut results = Vec::new();
 
         for item in data {
             // Имитация асинхронной операции
-            sleep(Duration::from_millis(100)).await;
-            
-            match self.provider.process_data(&item) {
+            self.clock.sleep(Duration::from_millis(100)).instrument(span.clone()).await;
+
+            let processed = span.in_scope(|| {
+                tracing::debug!(item = %item, "обработка элемента");
+                self.provider.process_data(&item)
+            });
+            match processed {
                 Ok(result) => results.push(result),
                 Err(e) => return Err(e),
             }
@@ -93,7 +273,7 @@ pub async fn filter_data(&self, predicate: &str) -> Result<Vec<String>, String>
         for item in data {
             if item.contains(predicate) {
                 // Имитация асинхронной операции
-                sleep(Duration::from_millis(50)).await;
+                self.clock.sleep(Duration::from_millis(50)).await;
                 
                 match self.provider.process_data(&item) {
                     Ok(result) => results.push(result),
@@ -162,8 +342,111 @@ pub async fn demonstrate_async_testing() -> Result<(), Box<dyn std::error::Error
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algorithms::{SearchingAlgorithms, SortingAlgorithms};
     use mockall::predicate::*;
 
+    /// Поиск индекса `target` в отсортированном массиве
+    type SearchFn = fn(&[i32], &i32) -> Option<usize>;
+
+    /// Сортировка массива на месте
+    type SortFn = fn(&mut [i32]);
+
+    const SEARCHES: [(&str, SearchFn); 5] = [
+        ("linear_search", SearchingAlgorithms::linear_search),
+        ("binary_search", SearchingAlgorithms::binary_search),
+        ("hash_search", SearchingAlgorithms::hash_search),
+        ("exponential_search", SearchingAlgorithms::exponential_search),
+        ("fibonacci_search", SearchingAlgorithms::fibonacci_search),
+    ];
+
+    const SORTS: [(&str, SortFn); 5] = [
+        ("quick_sort", SortingAlgorithms::quick_sort),
+        ("merge_sort", SortingAlgorithms::merge_sort),
+        ("insertion_sort", SortingAlgorithms::insertion_sort),
+        ("heap_sort", SortingAlgorithms::heap_sort),
+        ("tim_sort", SortingAlgorithms::tim_sort),
+    ];
+
+    /// Проверка всех алгоритмов поиска по таблице (массив, цель, ожидаемый индекс)
+    ///
+    /// Если цель встречается несколько раз, алгоритмы вправе вернуть любой
+    /// из ее индексов, поэтому точный индекс сверяется только для уникальной цели.
+    fn run_search_tests(cases: &[(&[i32], i32, Option<usize>)]) {
+        for &(arr, target, expected) in cases {
+            let unique = arr.iter().filter(|&&x| x == target).count() == 1;
+            for (name, search) in SEARCHES {
+                let found = search(arr, &target);
+                let context = format!("{}({:?}, {})", name, arr, target);
+                match expected {
+                    None => assert_eq!(found, None, "{}", context),
+                    Some(_) if unique => assert_eq!(found, expected, "{}", context),
+                    Some(_) => {
+                        let index = found.unwrap_or_else(|| panic!("{}: цель не найдена", context));
+                        assert_eq!(arr[index], target, "{}", context);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Проверка всех алгоритмов сортировки по таблице входных массивов
+    fn run_sort_tests(cases: &[&[i32]]) {
+        for &case in cases {
+            let mut expected = case.to_vec();
+            expected.sort();
+            for (name, sort) in SORTS {
+                let mut actual = case.to_vec();
+                sort(&mut actual);
+                assert_eq!(actual, expected, "{}({:?})", name, case);
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_table() {
+        let odd: Vec<i32> = (0..100).map(|i| 2 * i + 1).collect();
+        run_search_tests(&[
+            // Пустой массив
+            (&[], 5, None),
+            // Один элемент
+            (&[7], 7, Some(0)),
+            (&[7], 3, None),
+            (&[7], 9, None),
+            // Все элементы равны
+            (&[4, 4, 4, 4, 4], 4, Some(0)),
+            (&[4, 4, 4, 4, 4], 3, None),
+            (&[4, 4, 4, 4, 4], 5, None),
+            // Цель на границах и за ними
+            (&[1, 3, 5, 7, 9, 11], 1, Some(0)),
+            (&[1, 3, 5, 7, 9, 11], 11, Some(5)),
+            (&[1, 3, 5, 7, 9, 11], 0, None),
+            (&[1, 3, 5, 7, 9, 11], 12, None),
+            (&[1, 3, 5, 7, 9, 11], 6, None),
+            (&[1, 2], 1, Some(0)),
+            (&[1, 2], 2, Some(1)),
+            (&odd, 1, Some(0)),
+            (&odd, 199, Some(99)),
+            (&odd, 101, Some(50)),
+            (&odd, 100, None),
+            (&[i32::MIN, 0, i32::MAX], i32::MIN, Some(0)),
+            (&[i32::MIN, 0, i32::MAX], i32::MAX, Some(2)),
+        ]);
+    }
+
+    #[test]
+    fn test_sort_table() {
+        run_sort_tests(&[
+            &[],
+            &[1],
+            &[2, 1],
+            &[4, 4, 4, 4],
+            &[1, 2, 3, 4, 5],
+            &[5, 4, 3, 2, 1],
+            &[3, -1, 3, 0, -1, 2],
+            &[i32::MAX, i32::MIN, 0],
+        ]);
+    }
+
     #[test]
     fn test_filter_data() {
         let demo = TestDemo::new(vec![
@@ -224,4 +507,181 @@ async fn test_async_filter_data() {
         let result = demo.filter_data("test").await.unwrap();
         assert_eq!(result, vec!["processed_test1"]);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_process_data_with_mock_clock() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let processed = Arc::new(AtomicUsize::new(0));
+        let counter = processed.clone();
+        let mut mock_provider = MockDataProvider::new();
+        mock_provider
+            .expect_get_data()
+            .returning(|| (0..100).map(|i| i.to_string()).collect());
+        mock_provider.expect_process_data().returning(move |data| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(data.to_string())
+        });
+
+        let clock = Arc::new(MockClock::new());
+        let demo = AsyncTestDemo::new(Box::new(mock_provider)).with_clock(clock.clone());
+        let started = Instant::now();
+        let mut task = tokio_test::task::spawn(demo.process_data());
+        assert!(task.poll().is_pending());
+        assert_eq!(processed.load(Ordering::SeqCst), 0);
+
+        // 5 секунд по 100 мс на элемент: каждый сдвиг обрабатывает ровно один элемент
+        for step in 1..=50 {
+            clock.advance(Duration::from_millis(100));
+            assert!(task.poll().is_pending());
+            assert_eq!(processed.load(Ordering::SeqCst), step);
+        }
+        assert_eq!(clock.elapsed(), Duration::from_secs(5));
+        // Реальные 5 секунд не прошли; граница с большим запасом против нагрузки CI
+        assert!(started.elapsed() < Duration::from_secs(1), "{:?}", started.elapsed());
+
+        // Без сдвига часов обработка не продвигается
+        assert!(task.poll().is_pending());
+        assert_eq!(processed.load(Ordering::SeqCst), 50);
+    }
+
+    /// Настоящий провайдер: фиксированные данные, обработка в верхний регистр
+    struct StaticDataProvider(Vec<String>);
+
+    impl DataProvider for StaticDataProvider {
+        fn get_data(&self) -> Vec<String> {
+            self.0.clone()
+        }
+
+        fn process_data(&self, data: &str) -> Result<String, String> {
+            Ok(data.to_uppercase())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spy_records_calls() {
+        let provider = StaticDataProvider(vec!["foo".to_string(), "bar".to_string()]);
+        let spy = SpyDataProvider::new(Box::new(provider));
+        let calls = spy.calls();
+
+        let demo = AsyncTestDemo::new(Box::new(spy));
+        assert_eq!(demo.process_data().await.unwrap(), vec!["FOO", "BAR"]);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["get_data()", r#"process_data("foo")"#, r#"process_data("bar")"#]
+        );
+
+        calls.lock().unwrap().clear();
+        assert_eq!(demo.filter_data("ba").await.unwrap(), vec!["BAR"]);
+        assert_eq!(*calls.lock().unwrap(), vec!["get_data()", r#"process_data("bar")"#]);
+    }
+
+    #[tokio::test]
+    async fn test_process_data_span_events() {
+        use tracing_subscriber::fmt::format::FmtSpan;
+
+        let output = CapturedOutput::default();
+        let writer = output.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_span_events(FmtSpan::ENTER | FmtSpan::EXIT)
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut mock_provider = MockDataProvider::new();
+        mock_provider
+            .expect_get_data()
+            .returning(|| vec!["a".to_string(), "b".to_string()]);
+        mock_provider
+            .expect_process_data()
+            .returning(|data| Ok(data.to_uppercase()));
+        let demo = AsyncTestDemo::new(Box::new(mock_provider));
+        assert_eq!(demo.process_data().await.unwrap(), vec!["A", "B"]);
+
+        let log = output.contents();
+        assert!(log.lines().any(|line| line.contains("process_data") && line.ends_with("enter")), "{}", log);
+        assert!(log.lines().any(|line| line.contains("process_data") && line.ends_with("exit")), "{}", log);
+        // Событие внутри in_scope записано в контексте спана
+        assert!(
+            log.lines().any(|line| line.contains("process_data:") && line.ends_with("обработка элемента item=b")),
+            "{}",
+            log
+        );
+    }
+}
+
+/// Тестирование свойств алгоритмов сортировки
+#[cfg(test)]
+mod property_tests {
+    use crate::algorithms::SortingAlgorithms;
+    use proptest::prelude::*;
+    use std::cmp::Ordering;
+
+    /// Элемент, который сравнивается только по ключу, а не по исходному индексу
+    #[derive(Debug, Clone, Copy)]
+    struct Keyed(i32, usize);
+
+    impl PartialEq for Keyed {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Keyed {}
+
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    /// Сортировка на месте, проверяемая против `slice::sort`
+    type SortFn = fn(&mut [i32]);
+
+    /// Произвольный массив длиной от 0 до 1000 элементов
+    fn arbitrary_vec() -> impl Strategy<Value = Vec<i32>> {
+        prop::collection::vec(any::<i32>(), 0..=1000)
+    }
+
+    proptest! {
+        #[test]
+        fn sorts_match_std_sort(arr in arbitrary_vec()) {
+            let mut expected = arr.clone();
+            expected.sort();
+
+            let sorts: [(&str, SortFn); 4] = [
+                ("quick_sort", SortingAlgorithms::quick_sort),
+                ("merge_sort", SortingAlgorithms::merge_sort),
+                ("insertion_sort", SortingAlgorithms::insertion_sort),
+                ("heap_sort", SortingAlgorithms::heap_sort),
+            ];
+            for (name, sort) in sorts {
+                let mut actual = arr.clone();
+                sort(&mut actual);
+                prop_assert_eq!(&actual, &expected, "{} отсортировал неверно", name);
+            }
+        }
+
+        #[test]
+        fn insertion_sort_is_stable(keys in prop::collection::vec(0..10i32, 0..=1000)) {
+            // Узкий диапазон ключей гарантирует много равных элементов
+            let mut items: Vec<Keyed> = keys.iter().enumerate().map(|(i, &k)| Keyed(k, i)).collect();
+            SortingAlgorithms::insertion_sort(&mut items);
+
+            for pair in items.windows(2) {
+                prop_assert!(pair[0].0 <= pair[1].0);
+                if pair[0].0 == pair[1].0 {
+                    prop_assert!(pair[0].1 < pair[1].1, "нарушен порядок равных: {:?}", pair);
+                }
+            }
+        }
+    }
+}
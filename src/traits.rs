@@ -6,9 +6,19 @@
 //! - Ассоциированные типы
 //! - Трейты с ограничениями
 //! - Трейты с реализациями по умолчанию
+//! - Трейты с ограничениями на ассоциированный тип
+//! - Паттерн наблюдатель на трейт-объектах
+//! - Паттерн строитель
 
 use std::fmt;
 use std::ops::Add;
+use std::sync::Arc;
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::database;
 
 /// Трейт для объектов, которые можно сериализовать
 pub trait Serializable {
@@ -31,7 +41,37 @@ pub trait Container {
     fn get(&self, index: usize) -> Option<&Self::Item>;
     
     /// Удаление элемента по индексу
-    fn remove(&mut self, index: usize) -> Option<Self::Item>;
+    ///
+    /// По умолчанию всегда возвращает `None`, не изменяя контейнер:
+    /// контейнеры только для добавления могут не переопределять этот метод.
+    fn remove(&mut self, _index: usize) -> Option<Self::Item> {
+        None
+    }
+
+    /// Количество элементов
+    ///
+    /// По умолчанию элементы пересчитываются через `get` за O(n),
+    /// контейнеры с известной длиной должны переопределить метод.
+    fn len(&self) -> usize {
+        (0..).take_while(|&index| self.get(index).is_some()).count()
+    }
+
+    /// Проверка на пустоту
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Контейнер, содержимое которого можно сохранить в JSON и восстановить
+pub trait SerializableContainer: Container + Sized
+where
+    Self::Item: Serialize + DeserializeOwned,
+{
+    /// Сериализация элементов в JSON массив
+    fn to_json(&self) -> String;
+
+    /// Восстановление контейнера из JSON массива
+    fn from_json(json: &str) -> Result<Self, serde_json::Error>;
 }
 
 /// Трейт для объектов с реализацией по умолчанию
@@ -102,6 +142,23 @@ fn remove(&mut self, index: usize) -> Option<T> {
             None
         }
     }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> SerializableContainer for DynamicArray<T> {
+    fn to_json(&self) -> String {
+        // Vec сериализуется в JSON без ошибок, если это умеет сам T
+        serde_json::to_string(&self.items).expect("элементы не сериализуются в JSON")
+    }
+
+    fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            items: serde_json::from_str(json)?,
+        })
+    }
 }
 
 impl Printable for Document {
@@ -139,6 +196,114 @@ fn multiply(&self, other: &Complex) -> Complex {
     }
 }
 
+/// Подписчик на события типа `E`
+pub trait Listener<E>: Send + Sync {
+    fn on_event(&self, event: &E);
+}
+
+/// Источник событий: рассылает каждое событие всем подписчикам по порядку подписки
+pub struct EventEmitter<E> {
+    listeners: Vec<Arc<dyn Listener<E>>>,
+}
+
+impl<E> EventEmitter<E> {
+    /// Создание источника без подписчиков
+    pub fn new() -> Self {
+        Self { listeners: Vec::new() }
+    }
+
+    /// Подписка; один и тот же подписчик может быть общим для нескольких источников
+    pub fn subscribe(&mut self, listener: Arc<dyn Listener<E>>) {
+        self.listeners.push(listener);
+    }
+
+    /// Рассылка события всем подписчикам
+    pub fn emit(&self, event: &E) {
+        for listener in &self.listeners {
+            listener.on_event(event);
+        }
+    }
+
+    /// Количество подписчиков
+    pub fn listener_count(&self) -> usize {
+        self.listeners.len()
+    }
+}
+
+impl<E> Default for EventEmitter<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ошибки проверки данных в `UserBuilder` и `database::sanitize_user_input`
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("Имя пользователя не может быть пустым")]
+    EmptyName,
+
+    #[error("Некорректный email: {0:?}")]
+    InvalidEmail(String),
+
+    #[error("Ввод содержит SQL комментарий {0:?}")]
+    SqlComment(&'static str),
+
+    #[error("Ввод содержит нулевой байт")]
+    NullByte,
+
+    #[error("Ввод длиной {0} символов превышает {max}", max = crate::database::MAX_INPUT_LENGTH)]
+    TooLong(usize),
+}
+
+/// Строитель пользователя базы данных с проверкой полей
+///
+/// Пользователь еще не сохранен, поэтому получает `id = 0` и текущее время создания.
+#[derive(Debug, Default)]
+pub struct UserBuilder {
+    name: String,
+    email: String,
+}
+
+impl UserBuilder {
+    /// Имя пользователя
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Email пользователя
+    pub fn email(mut self, email: &str) -> Self {
+        self.email = email.to_string();
+        self
+    }
+
+    /// Проверка полей и создание пользователя
+    pub fn build(self) -> Result<database::User, ValidationError> {
+        if self.name.trim().is_empty() {
+            return Err(ValidationError::EmptyName);
+        }
+        if !self.email.contains('@') {
+            return Err(ValidationError::InvalidEmail(self.email));
+        }
+
+        Ok(database::User {
+            id: 0,
+            name: self.name,
+            email: self.email,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// Подписчик, печатающий события
+struct PrintListener;
+
+impl<E: fmt::Debug> Listener<E> for PrintListener {
+    fn on_event(&self, event: &E) {
+        println!("Получено событие: {:?}", event);
+    }
+}
+
 /// Демонстрация трейтов
 pub fn demonstrate_traits() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n=== Демонстрация трейтов ===");
@@ -162,6 +327,8 @@ pub fn demonstrate_traits() -> Result<(), Box<dyn std::error::Error>> {
     array.add(3);
     println!("Элемент по индексу 1: {:?}", array.get(1));
     println!("Удаленный элемент: {:?}", array.remove(1));
+    println!("Осталось элементов: {}", array.len());
+    println!("В JSON: {}", array.to_json());
 
     // Демонстрация печати
     println!("\n3. Печать:");
@@ -179,6 +346,18 @@ pub fn demonstrate_traits() -> Result<(), Box<dyn std::error::Error>> {
     println!("Сумма: {:?}", sum);
     println!("Произведение: {:?}", product);
 
+    // Демонстрация наблюдателя
+    println!("\n5. Наблюдатель:");
+    let mut emitter = EventEmitter::new();
+    emitter.subscribe(Arc::new(PrintListener));
+    emitter.emit(&"документ сохранен");
+
+    // Демонстрация строителя
+    println!("\n6. Строитель:");
+    let built = UserBuilder::default().name("Alice").email("alice@example.com").build()?;
+    println!("Построен пользователь: {:?}", built);
+    println!("Без email: {:?}", UserBuilder::default().name("Bob").build());
+
     Ok(())
 }
 
@@ -223,4 +402,115 @@ fn test_math_operations() {
         assert_eq!(sum.real, 4.0);
         assert_eq!(sum.imag, 6.0);
     }
+
+    #[test]
+    fn test_container_remove_and_len() {
+        let mut array = DynamicArray { items: vec![] };
+        assert!(array.is_empty());
+        array.add("a");
+        array.add("b");
+        array.add("c");
+        assert_eq!(array.len(), 3);
+
+        assert_eq!(array.remove(1), Some("b"));
+        assert_eq!(array.remove(5), None);
+        assert_eq!((array.get(0), array.get(1)), (Some(&"a"), Some(&"c")));
+        assert_eq!(array.len(), 2);
+        assert!(!array.is_empty());
+    }
+
+    #[test]
+    fn test_container_default_remove() {
+        // Контейнер только для добавления использует реализации по умолчанию
+        struct AppendOnly(Vec<u8>);
+
+        impl Container for AppendOnly {
+            type Item = u8;
+
+            fn add(&mut self, item: u8) {
+                self.0.push(item);
+            }
+
+            fn get(&self, index: usize) -> Option<&u8> {
+                self.0.get(index)
+            }
+        }
+
+        let mut log = AppendOnly(Vec::new());
+        log.add(1);
+        log.add(2);
+        assert_eq!(log.remove(0), None);
+        assert_eq!(log.len(), 2);
+    }
+
+    #[test]
+    fn test_serializable_container() {
+        let mut array = DynamicArray { items: vec![] };
+        for value in [3, 1, 2] {
+            array.add(value);
+        }
+
+        let json = array.to_json();
+        assert_eq!(json, "[3,1,2]");
+
+        let restored = DynamicArray::<i32>::from_json(&json).unwrap();
+        assert_eq!(restored.items, array.items);
+        assert!(DynamicArray::<i32>::from_json("[1, \"два\"]").is_err());
+    }
+
+    struct RecordingListener {
+        name: &'static str,
+        log: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl Listener<String> for RecordingListener {
+        fn on_event(&self, event: &String) {
+            self.log.lock().unwrap().push(format!("{}: {}", self.name, event));
+        }
+    }
+
+    #[test]
+    fn test_event_emitter() {
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut emitter = EventEmitter::new();
+        emitter.subscribe(Arc::new(RecordingListener { name: "a", log: log.clone() }));
+        emitter.subscribe(Arc::new(RecordingListener { name: "b", log: log.clone() }));
+        assert_eq!(emitter.listener_count(), 2);
+
+        for event in ["first", "second", "third"] {
+            emitter.emit(&event.to_string());
+        }
+
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 6);
+        for name in ["a", "b"] {
+            let received: Vec<&String> = log.iter().filter(|entry| entry.starts_with(name)).collect();
+            assert_eq!(received.len(), 3);
+            assert!(received[2].ends_with("third"));
+        }
+    }
+
+    #[test]
+    fn test_user_builder() {
+        let user = UserBuilder::default()
+            .name("Alice")
+            .email("alice@example.com")
+            .build()
+            .unwrap();
+        assert_eq!(user.name, "Alice");
+        assert_eq!(user.email, "alice@example.com");
+
+        assert_eq!(
+            UserBuilder::default().email("alice@example.com").build().unwrap_err(),
+            ValidationError::EmptyName
+        );
+        assert_eq!(
+            UserBuilder::default().name("Alice").build().unwrap_err(),
+            ValidationError::InvalidEmail(String::new())
+        );
+        assert!(matches!(
+            UserBuilder::default().name("Alice").email("alice.example.com").build(),
+            Err(ValidationError::InvalidEmail(_))
+        ));
+    }
 } 
\ No newline at end of file